@@ -15,11 +15,11 @@ use hash::{Blake2b224, Sha3_256};
 
 use cbor;
 use cbor_event::{self, de::Deserializer, se::Serializer};
-use config::NetworkMagic;
-use hdpayload::HDAddressPayload;
+use config::{NetworkMagic, ProtocolMagic};
+use hdpayload::{HDAddressPayload, HDKey, Path};
 use hdwallet::XPub;
 use redeem;
-use util::{base58, try_from_slice::TryFromSlice};
+use util::{base58, crc32::crc32, try_from_slice::TryFromSlice};
 
 use std::{
     fmt,
@@ -553,20 +553,103 @@ impl ExtendedAddr {
         )
     }
 
+    /// create the public key address of the given `XPub`. The network
+    /// magic attribute of `attrs` is set from `protocol_magic`.
+    pub fn from_xpub(xpub: &XPub, attrs: Attributes, protocol_magic: ProtocolMagic) -> Self {
+        let attrs = Attributes {
+            network_magic: protocol_magic.into(),
+            ..attrs
+        };
+        ExtendedAddr::new(AddrType::ATPubKey, SpendingData::PubKeyASD(*xpub), attrs)
+    }
+
+    /// create the redeem address of the given redeem public key, as
+    /// found in the genesis UTxO.
+    pub fn from_redeem_pubkey(pk: &redeem::PublicKey, protocol_magic: ProtocolMagic) -> Self {
+        ExtendedAddr::new(
+            AddrType::ATRedeem,
+            SpendingData::RedeemASD(*pk),
+            Attributes::new_bootstrap_era(None, protocol_magic.into()),
+        )
+    }
+
     pub fn to_address(&self) -> Addr {
         Addr(cbor!(self).unwrap()) // unwrap should never fail from strongly typed extended addr to addr
     }
+
+    /// the protocol magic of the network the address is for. Mainnet
+    /// addresses do not have the network magic attribute and return
+    /// `None`.
+    pub fn network_magic(&self) -> Option<ProtocolMagic> {
+        match self.attributes.network_magic {
+            NetworkMagic::NoMagic => None,
+            NetworkMagic::Magic(magic) => Some(ProtocolMagic::from(magic)),
+        }
+    }
+
+    /// check if the address belongs to the wallet of the given `HDKey`
+    /// by decrypting the derivation path attribute. Returns the
+    /// derivation path on success.
+    ///
+    /// Only bootstrap era addresses with a derivation path can be
+    /// recognised this way; `None` is returned for any other address.
+    pub fn is_owned_by(&self, hdkey: &HDKey) -> Option<Path> {
+        match &self.attributes.derivation_path {
+            &Some(ref payload) => hdkey.decrypt_path(payload).ok(),
+            &None => None,
+        }
+    }
 }
 #[derive(Debug)]
 pub enum ParseExtendedAddrError {
     EncodingError(cbor_event::Error),
     Base58Error(base58::Error),
+    /// the crc32 stored in the address does not match the crc32 of the
+    /// address's content (typically a typo in a copy-pasted address)
+    ChecksumError {
+        expected: u32,
+        found: u32,
+    },
+}
+impl fmt::Display for ParseExtendedAddrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseExtendedAddrError::EncodingError(_) => write!(f, "invalid address encoding"),
+            ParseExtendedAddrError::Base58Error(_) => write!(f, "invalid base58 address"),
+            ParseExtendedAddrError::ChecksumError { expected, found } => write!(
+                f,
+                "invalid address checksum: 0x{:x} but expected 0x{:x}",
+                found, expected
+            ),
+        }
+    }
+}
+impl ::std::error::Error for ParseExtendedAddrError {
+    fn cause(&self) -> Option<&::std::error::Error> {
+        match self {
+            ParseExtendedAddrError::EncodingError(ref err) => Some(err),
+            ParseExtendedAddrError::Base58Error(ref err) => Some(err),
+            ParseExtendedAddrError::ChecksumError { .. } => None,
+        }
+    }
 }
 impl ::std::str::FromStr for ExtendedAddr {
     type Err = ParseExtendedAddrError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let bytes = base58::decode(s).map_err(ParseExtendedAddrError::Base58Error)?;
 
+        // check the crc32 first so a mistyped address is reported as such
+        // rather than as a generic encoding error
+        {
+            let mut raw = Deserializer::from(std::io::Cursor::new(&bytes));
+            let (content, expected) = cbor::hs::util::raw_and_crc32(&mut raw)
+                .map_err(ParseExtendedAddrError::EncodingError)?;
+            let found = crc32(&content);
+            if expected != found {
+                return Err(ParseExtendedAddrError::ChecksumError { expected, found });
+            }
+        }
+
         Self::try_from_slice(&bytes).map_err(ParseExtendedAddrError::EncodingError)
     }
 }
@@ -818,6 +901,73 @@ mod tests {
         assert!(cbor_event::test_encode_decode(&si).expect("encode/decode StakeholderId"));
     }
     #[test]
+    fn redeem_address_from_pubkey() {
+        let pk =
+            redeem::PublicKey::from_base64("-0BJDi-gauylk4LptQTgjMeo7kY9lTCbZv12vwOSTZk=").unwrap();
+        let addr = ExtendedAddr::from_redeem_pubkey(&pk, ProtocolMagic::default());
+        assert_eq!(addr.addr_type, AddrType::ATRedeem);
+        assert_eq!(
+            format!("{}", addr),
+            "Ae2tdPwUPEZHFQnrr2dYB4GEQ8WVKspEyrg29pJ3f7qdjzaxjeShEEokF5f"
+        );
+    }
+
+    #[test]
+    fn address_from_xpub() {
+        let mut bytes = [0; hdwallet::XPUB_SIZE];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let xpub = hdwallet::XPub::from_bytes(bytes);
+        let attrs = Attributes::new_bootstrap_era(None, NetworkMagic::NoMagic);
+
+        let addr = ExtendedAddr::from_xpub(&xpub, attrs.clone(), ProtocolMagic::default());
+        assert_eq!(addr.addr_type, AddrType::ATPubKey);
+        assert_eq!(
+            format!("{}", addr),
+            "Ae2tdPwUPEZ987QhEbj7Fs5DBudHvZzzgud3kjcnxPF1KVF4eWTbKwmQgs1"
+        );
+        assert_eq!(addr, ExtendedAddr::new_simple(xpub, NetworkMagic::NoMagic));
+
+        let addr = ExtendedAddr::from_xpub(&xpub, attrs, ProtocolMagic::from(1097911063));
+        assert_eq!(
+            format!("{}", addr),
+            "2cWKMJemoBaiv3CXrYL1N6wx7eHwGx7iwCqqSAaxbSmafc3Rd8b7g9AQsPD6on6b3Vu5h"
+        );
+    }
+
+    #[test]
+    fn address_network_magic() {
+        let mainnet: ExtendedAddr = "Ae2tdPwUPEZHFQnrr2dYB4GEQ8WVKspEyrg29pJ3f7qdjzaxjeShEEokF5f"
+            .parse()
+            .unwrap();
+        assert_eq!(mainnet.network_magic(), None);
+
+        let seed = hdwallet::Seed::from_bytes([0; hdwallet::SEED_SIZE]);
+        let pk = hdwallet::XPrv::generate_from_seed(&seed).public();
+        let testnet = ExtendedAddr::new_simple(pk, NetworkMagic::Magic(1097911063));
+        let testnet: ExtendedAddr = format!("{}", testnet).parse().unwrap();
+        assert_eq!(
+            testnet.network_magic(),
+            Some(ProtocolMagic::from(1097911063))
+        );
+    }
+
+    #[test]
+    fn stakeholderid_from_xpub() {
+        // blake2b_224(sha3_256(cbor(xpub)))
+        let mut bytes = [0; hdwallet::XPUB_SIZE];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let si = StakeholderId::new(&hdwallet::XPub::from_bytes(bytes));
+        assert_eq!(
+            format!("{}", si),
+            "cdf621c7382b7b5c34f0c144f08916fe1d9b6b0d3feb89dcc61959df"
+        );
+        assert_eq!(si.as_hash_bytes().len(), 28);
+    }
+    #[test]
     fn encode_decode_stakedistribution() {
         use hdwallet;
         let seed = hdwallet::Seed::from_bytes([0; hdwallet::SEED_SIZE]);
@@ -844,6 +994,9 @@ mod tests {
             StakeDistribution::BootstrapEraDistr
         );
         assert_eq!(r.attributes.network_magic, NetworkMagic::NoMagic);
+        // random index wallet address: the encrypted derivation path is present
+        let payload = r.attributes.derivation_path.as_ref().unwrap();
+        assert!(payload.as_ref().len() > 16);
     }
 
     #[test]
@@ -864,6 +1017,53 @@ mod tests {
         assert_eq!(r.attributes.network_magic, NetworkMagic::NoMagic);
     }
 
+    #[test]
+    fn parse_address_from_str() {
+        let addr_str  = "DdzFFzCqrhsi8XFMabbnHecVusaebqQCkXTqDnCumx5esKB1pk1zbhX5BtdAivZbQePFVujgzNCpBVXactPSmphuHRC5Xk8qmBd49QjW";
+
+        let r: ExtendedAddr = addr_str.parse().unwrap();
+
+        assert_eq!(r.addr_type, AddrType::ATPubKey);
+        assert_eq!(addr_str, format!("{}", r));
+    }
+
+    #[test]
+    fn parse_address_from_str_invalid_checksum() {
+        let addr_str  = "DdzFFzCqrhsi8XFMabbnHecVusaebqQCkXTqDnCumx5esKB1pk1zbhX5BtdAivZbQePFVujgzNCpBVXactPSmphuHRC5Xk8qmBd49QjW";
+        let mut bytes = base58::decode(addr_str).unwrap();
+        // the crc32 is the trailing integer of the cbor envelope
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0x01;
+
+        match base58::encode(&bytes).parse::<ExtendedAddr>() {
+            Err(ParseExtendedAddrError::ChecksumError { .. }) => {}
+            r => panic!("expected a checksum error, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn parse_address_from_str_invalid_envelope() {
+        let addr_str  = "DdzFFzCqrhsi8XFMabbnHecVusaebqQCkXTqDnCumx5esKB1pk1zbhX5BtdAivZbQePFVujgzNCpBVXactPSmphuHRC5Xk8qmBd49QjW";
+        let mut bytes = base58::decode(addr_str).unwrap();
+        // a 3 items array instead of `[tag(24, bytes), crc32]`
+        assert_eq!(bytes[0], 0x82);
+        bytes[0] = 0x83;
+        bytes.push(0x00);
+
+        match base58::encode(&bytes).parse::<ExtendedAddr>() {
+            Err(ParseExtendedAddrError::EncodingError(_)) => {}
+            r => panic!("expected an encoding error, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn parse_address_from_str_invalid_base58() {
+        match "DdzFFzCqrhs0".parse::<ExtendedAddr>() {
+            Err(ParseExtendedAddrError::Base58Error(_)) => {}
+            r => panic!("expected a base58 error, got {:?}", r),
+        }
+    }
+
     #[test]
     fn decode_address_no_derivation_path() {
         let bytes = vec![
@@ -881,6 +1081,7 @@ mod tests {
             StakeDistribution::BootstrapEraDistr
         );
         assert_eq!(r.attributes.network_magic, NetworkMagic::NoMagic);
+        assert_eq!(r.attributes.derivation_path, None);
         assert_eq!(bytes, cbor!(r).unwrap())
     }
 
@@ -898,6 +1099,44 @@ mod tests {
         assert_eq!(r.attributes.network_magic, NetworkMagic::Magic(1097911063));
         assert_eq!(&bytes[..], &cbor!(r).unwrap()[..])
     }
+
+    fn hd_address(root: &hdwallet::XPrv, path: &Path) -> ExtendedAddr {
+        let hdkey = HDKey::new(&root.public());
+        let payload = hdkey.encrypt_path(path);
+        ExtendedAddr::new(
+            AddrType::ATPubKey,
+            SpendingData::PubKeyASD(root.public()),
+            Attributes::new_bootstrap_era(Some(payload), NetworkMagic::NoMagic),
+        )
+    }
+
+    #[test]
+    fn address_is_owned_by() {
+        let seed = hdwallet::Seed::from_bytes([0; hdwallet::SEED_SIZE]);
+        let root = hdwallet::XPrv::generate_from_seed(&seed);
+        let path = Path::new(vec![0x80000000, 42]);
+        let addr = hd_address(&root, &path);
+
+        let hdkey = HDKey::new(&root.public());
+        assert_eq!(addr.is_owned_by(&hdkey), Some(path));
+    }
+
+    #[test]
+    fn address_is_not_owned_by() {
+        let seed = hdwallet::Seed::from_bytes([0; hdwallet::SEED_SIZE]);
+        let root = hdwallet::XPrv::generate_from_seed(&seed);
+        let addr = hd_address(&root, &Path::new(vec![0x80000000, 42]));
+
+        let other_seed = hdwallet::Seed::from_bytes([1; hdwallet::SEED_SIZE]);
+        let other_root = hdwallet::XPrv::generate_from_seed(&other_seed);
+        let other_hdkey = HDKey::new(&other_root.public());
+        assert_eq!(addr.is_owned_by(&other_hdkey), None);
+
+        // addresses without derivation path are never owned
+        let simple = ExtendedAddr::new_simple(root.public(), NetworkMagic::NoMagic);
+        let hdkey = HDKey::new(&root.public());
+        assert_eq!(simple.is_owned_by(&hdkey), None);
+    }
 }
 
 #[cfg(feature = "with-bench")]
@@ -3,7 +3,7 @@
 //! The main types are `Header` and `Block`
 use std::ops::{Deref, DerefMut};
 use std::{
-    fmt,
+    error, fmt,
     io::{BufRead, Cursor, Write},
 };
 
@@ -12,18 +12,21 @@ use super::super::config::ProtocolMagic;
 use super::boundary;
 use super::date::BlockDate;
 use super::normal;
-use super::types::{BlockVersion, HeaderHash};
+use super::types::{BlockVersion, ChainDifficulty, EpochId, HeaderHash};
+use crate::address::ExtendedAddr;
+use crate::hash::Blake2b256;
 use crate::tx::TxAux;
+use crate::util::hex;
 use cbor_event::{self, de::Deserialize, de::Deserializer, se::Serializer};
 use chain_core;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RawBlockHeaderMultiple(pub Vec<u8>);
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RawBlockHeader(pub Vec<u8>);
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RawBlock(pub Vec<u8>);
 
 impl RawBlockHeaderMultiple {
@@ -74,7 +77,7 @@ impl AsRef<[u8]> for RawBlock {
 }
 
 /// Block Header of either a boundary header or a normal header
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BlockHeader {
     BoundaryBlockHeader(boundary::BlockHeader),
     MainBlockHeader(normal::BlockHeader),
@@ -139,7 +142,7 @@ impl<'a> From<BlockHeaderView<'a>> for BlockHeader {
 
 /// BlockHeaders is a vector of block headers, as produced by
 /// MsgBlocks.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BlockHeaders(pub Vec<BlockHeader>);
 
 impl Deref for BlockHeaders {
@@ -172,6 +175,15 @@ impl<'a> BlockHeaderView<'a> {
         }
     }
 
+    /// Returns the chain difficulty, i.e. the number of main blocks
+    /// in the chain up to this block.
+    pub fn difficulty(&self) -> ChainDifficulty {
+        match self {
+            BlockHeaderView::Boundary(hdr) => hdr.consensus.chain_difficulty,
+            BlockHeaderView::Normal(hdr) => hdr.consensus.chain_difficulty,
+        }
+    }
+
     /// Returns true if the block is the epoch's boundary block,
     /// otherwise returns false.
     pub fn is_boundary_block(&self) -> bool {
@@ -271,13 +283,55 @@ impl chain_core::property::Deserialize for BlockHeader {
     }
 }
 
+/// error when parsing a block from its hexadecimal representation
+#[derive(Debug)]
+pub enum FromHexError {
+    /// the string is not a valid hexadecimal string
+    HexadecimalError(hex::Error),
+    /// the decoded bytes are not a valid CBOR encoded block
+    CborError(cbor_event::Error),
+}
+impl From<hex::Error> for FromHexError {
+    fn from(e: hex::Error) -> Self {
+        FromHexError::HexadecimalError(e)
+    }
+}
+impl From<cbor_event::Error> for FromHexError {
+    fn from(e: cbor_event::Error) -> Self {
+        FromHexError::CborError(e)
+    }
+}
+impl fmt::Display for FromHexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromHexError::HexadecimalError(_) => write!(f, "invalid hexadecimal block"),
+            FromHexError::CborError(_) => write!(f, "invalid CBOR block"),
+        }
+    }
+}
+impl error::Error for FromHexError {
+    fn cause(&self) -> Option<&error::Error> {
+        match self {
+            FromHexError::HexadecimalError(ref err) => Some(err),
+            FromHexError::CborError(ref err) => Some(err),
+        }
+    }
+}
+
 /// Block of either a boundary block or a normal block
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Block {
     BoundaryBlock(boundary::Block),
     MainBlock(normal::Block),
 }
 impl Block {
+    /// parse a block from the hexadecimal encoding of its raw bytes,
+    /// as found in logs for example.
+    pub fn from_hex(s: &str) -> Result<Self, FromHexError> {
+        let bytes = hex::decode(s)?;
+        Ok(RawBlock(bytes).decode()?)
+    }
+
     pub fn is_boundary_block(&self) -> bool {
         match self {
             &Block::BoundaryBlock(_) => true,
@@ -285,6 +339,12 @@ impl Block {
         }
     }
 
+    /// the epoch the block belongs to, a boundary block starts the
+    /// epoch it belongs to.
+    pub fn epoch(&self) -> EpochId {
+        self.header().blockdate().get_epochid()
+    }
+
     pub fn header(&self) -> BlockHeaderView {
         match self {
             Block::BoundaryBlock(blk) => BlockHeaderView::Boundary(&blk.header),
@@ -314,12 +374,65 @@ impl Block {
         }
     }
 
+    /// the transactions of the block, without cloning them. Boundary
+    /// blocks have no transactions.
+    pub fn transactions(&self) -> &[TxAux] {
+        match self {
+            &Block::BoundaryBlock(_) => &[],
+            &Block::MainBlock(ref blk) => &blk.body.tx,
+        }
+    }
+
+    /// the addresses of all the outputs of the block's transactions,
+    /// in order. Boundary blocks have no outputs.
+    pub fn output_addresses(&self) -> Vec<&ExtendedAddr> {
+        self.transactions()
+            .iter()
+            .flat_map(|txaux| txaux.tx.outputs.iter())
+            .map(|output| &output.address)
+            .collect()
+    }
+
     pub fn get_protocol_magic(&self) -> ProtocolMagic {
         match self {
             &Block::BoundaryBlock(ref blk) => blk.header.protocol_magic,
             &Block::MainBlock(ref blk) => blk.header.protocol_magic,
         }
     }
+
+    /// Serializes the block into its raw data representation, as
+    /// sent over the network or stored on disk.
+    pub fn to_raw(&self) -> RawBlock {
+        // the only reason this would fail is if there was no more memory
+        // to allocate. This would be the users' last concern if it was the
+        // case
+        RawBlock(cbor!(self).unwrap())
+    }
+
+    /// Computes the hash of the block header, this is the hash the
+    /// next block of the chain refers to as its previous header.
+    pub fn header_hash(&self) -> HeaderHash {
+        self.header().compute_hash()
+    }
+
+    /// Checks that this block is chained to the block whose header
+    /// hash is `prev`.
+    pub fn verify_prev(&self, prev: &HeaderHash) -> bool {
+        &self.header().previous_header() == prev
+    }
+
+    /// Checks that the body of the block matches the proof
+    /// committed to in its header.
+    pub fn verify_body_proof(&self) -> bool {
+        match self {
+            &Block::BoundaryBlock(ref blk) => {
+                Blake2b256::new(&cbor!(&blk.body).unwrap()) == blk.header.body_proof.0
+            }
+            &Block::MainBlock(ref blk) => {
+                normal::BodyProof::generate_from_body(&blk.body) == blk.header.body_proof
+            }
+        }
+    }
 }
 
 impl fmt::Display for Block {
@@ -2,13 +2,14 @@ use super::types::{self, ChainDifficulty, HeaderHash};
 use crate::{address, config::ProtocolMagic, hash::Blake2b256};
 
 use std::{
+    collections::BTreeMap,
     fmt,
     io::{BufRead, Write},
 };
 
 use cbor_event::{self, de::Deserializer, se::Serializer};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BodyProof(pub Blake2b256);
 impl fmt::Display for BodyProof {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -30,10 +31,17 @@ impl cbor_event::de::Deserialize for BodyProof {
 }
 
 /// Genesis block body
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Body {
     pub slot_leaders: Vec<address::StakeholderId>,
 }
+impl Body {
+    /// the stakeholder scheduled to produce the block of the given
+    /// slot of the epoch, `None` if the slot is out of the epoch.
+    pub fn leader_for_slot(&self, slot: u32) -> Option<&address::StakeholderId> {
+        self.slot_leaders.get(slot as usize)
+    }
+}
 impl cbor_event::se::Serialize for Body {
     fn serialize<'se, W: Write>(
         &self,
@@ -62,7 +70,7 @@ impl cbor_event::de::Deserialize for Body {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BlockHeader {
     pub protocol_magic: ProtocolMagic,
     pub previous_header: HeaderHash,
@@ -125,13 +133,51 @@ impl cbor_event::de::Deserialize for BlockHeader {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Block {
     pub header: BlockHeader,
     pub body: Body,
     pub extra: cbor_event::Value,
 }
 
+impl Block {
+    /// Create the genesis block of the epoch 0.
+    ///
+    /// `previous_header` is the hash of the genesis data the chain
+    /// starts from. The body proof is computed from the given slot
+    /// leaders, so the header hash is reproducible.
+    pub fn new(
+        protocol_magic: ProtocolMagic,
+        previous_header: HeaderHash,
+        slot_leaders: Vec<address::StakeholderId>,
+        chain_difficulty: ChainDifficulty,
+    ) -> Self {
+        let body = Body { slot_leaders };
+        let body_proof = BodyProof(Blake2b256::new(&cbor!(&body).unwrap()));
+        let consensus = Consensus {
+            epoch: 0,
+            chain_difficulty,
+        };
+        let header = BlockHeader::new(
+            protocol_magic,
+            previous_header,
+            body_proof,
+            consensus,
+            types::BlockHeaderAttributes(empty_attributes()),
+        );
+        Block {
+            header,
+            body,
+            extra: empty_attributes(),
+        }
+    }
+}
+
+// the attributes of a boundary block: a list with an empty map
+fn empty_attributes() -> cbor_event::Value {
+    cbor_event::Value::Array(vec![cbor_event::Value::Object(BTreeMap::new())])
+}
+
 impl fmt::Display for Block {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "{}", self.header)?;
@@ -164,7 +210,7 @@ impl cbor_event::de::Deserialize for Block {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Consensus {
     pub epoch: types::EpochId,
     pub chain_difficulty: ChainDifficulty,
@@ -191,3 +237,54 @@ impl cbor_event::de::Deserialize for Consensus {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use address::StakeholderId;
+    use block::{self, RawBlock};
+    use hdwallet::{XPub, XPUB_SIZE};
+
+    #[test]
+    fn genesis_block_roundtrip() {
+        let slot_leaders = vec![
+            StakeholderId::new(&XPub::from_bytes([1; XPUB_SIZE])),
+            StakeholderId::new(&XPub::from_bytes([2; XPUB_SIZE])),
+        ];
+        let genesis_hash = HeaderHash::new(b"genesis data");
+        let blk = block::Block::BoundaryBlock(Block::new(
+            ProtocolMagic::default(),
+            genesis_hash.clone(),
+            slot_leaders.clone(),
+            ChainDifficulty::from(0),
+        ));
+        assert!(blk.verify_body_proof());
+
+        let raw = RawBlock(cbor!(&blk).unwrap());
+        let decoded = raw.decode().unwrap();
+        assert_eq!(cbor!(&decoded).unwrap(), raw.0);
+        assert_eq!(decoded.header_hash(), blk.header_hash());
+        assert!(decoded.verify_prev(&genesis_hash));
+        match decoded {
+            block::Block::BoundaryBlock(blk) => {
+                assert_eq!(blk.header.consensus.epoch, 0);
+                assert_eq!(blk.body.slot_leaders, slot_leaders);
+            }
+            block::Block::MainBlock(_) => panic!("expected a boundary block"),
+        }
+    }
+
+    #[test]
+    fn leader_for_slot() {
+        let leader0 = StakeholderId::new(&XPub::from_bytes([1; XPUB_SIZE]));
+        let leader1 = StakeholderId::new(&XPub::from_bytes([2; XPUB_SIZE]));
+        let body = Body {
+            slot_leaders: vec![leader0, leader1, leader0],
+        };
+        assert_eq!(body.leader_for_slot(0), Some(&leader0));
+        assert_eq!(body.leader_for_slot(1), Some(&leader1));
+        assert_eq!(body.leader_for_slot(2), Some(&leader0));
+        assert_eq!(body.leader_for_slot(3), None);
+        assert_eq!(body.leader_for_slot(u32::max_value()), None);
+    }
+}
@@ -2,7 +2,6 @@ use address;
 use block::*;
 use config::{GenesisData, ProtocolMagic};
 use fee;
-use hash;
 use std::collections::BTreeMap;
 use tx::{self, TxOut, TxoPointer};
 
@@ -46,7 +45,7 @@ impl ChainState {
 
         // Create utxos from non-AVVM balances.
         for (address, value) in &genesis_data.non_avvm_balances {
-            let id = hash::Blake2b256::new(&cbor!(&address).unwrap());
+            let id = tx::TxId::new(&cbor!(&address).unwrap());
             utxos.insert(
                 TxoPointer { id, index: 0 },
                 TxOut {
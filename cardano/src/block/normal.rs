@@ -4,15 +4,15 @@ use std::{
     fmt,
     io::{BufRead, Write},
 };
-use {address, hash::Blake2b256, hdwallet, tx, vss};
+use {address, hash::Blake2b256, hdwallet, tags, tx, vss};
 
-use super::sign::BlockSignature;
+use super::sign::{BlockSignature, MainToSign, SigError};
 use super::types;
 use super::types::{ChainDifficulty, EpochSlotId, HeaderExtraData, HeaderHash, SscProof};
 use super::update;
 use cbor_event::{self, de::Deserializer, se::Serializer};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BodyProof {
     pub tx: tx::TxProof,
     pub mpc: types::SscProof,
@@ -36,7 +36,7 @@ impl BodyProof {
 
     pub fn generate_from_body(body: &Body) -> Self {
         BodyProof {
-            tx: tx::TxProof::generate(&body.tx),
+            tx: body.compute_tx_proof(),
             mpc: SscProof::generate(&body.ssc),
             delegation: DlgProof::generate(&body.delegation),
             update: update::UpdateProof::generate(&body.update),
@@ -70,7 +70,7 @@ impl cbor_event::de::Deserialize for BodyProof {
 }
 
 /// Witness of delegation payload consisting of a simple hash
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DlgProof(pub Blake2b256);
 
 impl DlgProof {
@@ -102,7 +102,7 @@ impl cbor_event::de::Deserialize for DlgProof {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TxPayload {
     txaux: Vec<tx::TxAux>,
 }
@@ -175,7 +175,7 @@ impl cbor_event::de::Deserialize for TxPayload {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Body {
     pub tx: TxPayload,
     pub ssc: SscPayload,
@@ -196,6 +196,12 @@ impl Body {
             update,
         }
     }
+
+    /// Computes the proof of the transactions of the body: the merkle
+    /// root of the transactions and the hash of their witnesses.
+    pub fn compute_tx_proof(&self) -> tx::TxProof {
+        tx::TxProof::generate(&self.tx)
+    }
 }
 impl fmt::Display for Body {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -227,7 +233,7 @@ impl cbor_event::de::Deserialize for Body {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum SscPayload {
     CommitmentsPayload(Commitments, VssCertificates),
     OpeningsPayload(OpeningsMap, VssCertificates),
@@ -318,7 +324,7 @@ impl cbor_event::de::Deserialize for SscPayload {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DlgPayload(pub cbor_event::Value);
 
 impl cbor_event::de::Deserialize for DlgPayload {
@@ -336,7 +342,7 @@ impl cbor_event::se::Serialize for DlgPayload {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Commitments(Vec<SignedCommitment>);
 impl Commitments {
     pub fn iter(&self) -> ::std::slice::Iter<SignedCommitment> {
@@ -358,7 +364,7 @@ impl cbor_event::de::Deserialize for Commitments {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SignedCommitment {
     pub public_key: hdwallet::XPub,
     pub commitment: Commitment,
@@ -391,7 +397,7 @@ impl cbor_event::de::Deserialize for SignedCommitment {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Commitment {
     pub proof: SecretProof,
     pub shares: BTreeMap<vss::PublicKey, EncShare>,
@@ -416,7 +422,7 @@ impl cbor_event::de::Deserialize for Commitment {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SecretProof {
     pub extra_gen: cbor_event::Value, // TODO decode a http://hackage.haskell.org/package/pvss-0.2.0/docs/Crypto-SCRAPE.html#t:ExtraGen
     pub proof: cbor_event::Value, // TODO decode a http://hackage.haskell.org/package/pvss-0.2.0/docs/Crypto-SCRAPE.html#t:Proof
@@ -455,7 +461,7 @@ impl cbor_event::de::Deserialize for SecretProof {
 
 // TODO: decode to
 // http://hackage.haskell.org/package/pvss-0.2.0/docs/Crypto-SCRAPE.html#t:EncryptedSi
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EncShare(cbor_event::Value);
 impl cbor_event::se::Serialize for EncShare {
     fn serialize<'se, W: Write>(
@@ -473,7 +479,7 @@ impl cbor_event::de::Deserialize for EncShare {
 
 // TODO: decode value in this map to
 // http://hackage.haskell.org/package/pvss-0.2.0/docs/Crypto-SCRAPE.html#t:Secret
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct OpeningsMap(BTreeMap<address::StakeholderId, cbor_event::Value>);
 impl OpeningsMap {
     pub fn iter(&self) -> btree_map::Iter<address::StakeholderId, cbor_event::Value> {
@@ -494,7 +500,7 @@ impl cbor_event::de::Deserialize for OpeningsMap {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SharesMap(BTreeMap<address::StakeholderId, SharesSubMap>);
 pub type SharesSubMap = BTreeMap<address::StakeholderId, DecShare>;
 impl SharesMap {
@@ -523,7 +529,7 @@ impl cbor_event::de::Deserialize for SharesMap {
 
 // TODO: decode to
 // https://hackage.haskell.org/package/pvss-0.2.0/docs/Crypto-SCRAPE.html#t:DecryptedShare
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DecShare(cbor_event::Value);
 impl cbor_event::se::Serialize for DecShare {
     fn serialize<'se, W: Write>(
@@ -542,7 +548,7 @@ impl cbor_event::de::Deserialize for DecShare {
 // TODO: after we properly decode VssCertificate.vss_key, change this struct to a
 // BTreeMap<StakeholderId, VssCertificate> see
 // https://github.com/input-output-hk/cardano-sl/blob/005076eb3434444a505c0fb150ea98e56e8bb3d9/core/src/Pos/Core/Ssc/VssCertificatesMap.hs#L36-L44
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VssCertificates(Vec<VssCertificate>);
 impl VssCertificates {
     pub fn new(vss_certs: Vec<VssCertificate>) -> Self {
@@ -600,7 +606,7 @@ impl cbor_event::de::Deserialize for VssCertificates {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VssCertificate {
     pub vss_key: vss::PublicKey,
     pub expiry_epoch: types::EpochId,
@@ -637,7 +643,7 @@ impl cbor_event::de::Deserialize for VssCertificate {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BlockHeader {
     pub protocol_magic: ProtocolMagic,
     pub previous_header: HeaderHash,
@@ -701,7 +707,7 @@ impl cbor_event::de::Deserialize for BlockHeader {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Block {
     pub header: BlockHeader,
     pub body: Body,
@@ -715,6 +721,43 @@ impl Block {
             extra: e,
         }
     }
+
+    /// verify the block signature against the slot leader's key.
+    ///
+    /// For blocks signed by a delegate, the delegation certificate
+    /// needs to be issued by the slot leader and properly signed.
+    pub fn verify_signature(&self) -> Result<(), SigError> {
+        let hdr = &self.header;
+        let to_sign = MainToSign::from_header(hdr);
+        match &hdr.consensus.block_signature {
+            BlockSignature::Signature(sig) => {
+                if !to_sign.verify_sig(hdr.protocol_magic, &hdr.consensus.leader_key, sig) {
+                    return Err(SigError::BadSignature);
+                }
+            }
+            BlockSignature::ProxyLight(_) => return Err(SigError::UnsupportedSignature),
+            BlockSignature::ProxyHeavy(proxy_sig) => {
+                // check against self-signed PSKs
+                if proxy_sig.psk.issuer_pk == proxy_sig.psk.delegate_pk {
+                    return Err(SigError::SelfSignedPSK);
+                }
+                if proxy_sig.psk.issuer_pk != hdr.consensus.leader_key {
+                    return Err(SigError::WrongIssuer);
+                }
+                if !proxy_sig.psk.verify(hdr.protocol_magic) {
+                    return Err(SigError::BadDelegationCert);
+                }
+                if !to_sign.verify_proxy_sig(
+                    hdr.protocol_magic,
+                    tags::SigningTag::MainBlockHeavy,
+                    proxy_sig,
+                ) {
+                    return Err(SigError::BadSignature);
+                }
+            }
+        }
+        Ok(())
+    }
 }
 impl fmt::Display for Block {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -744,7 +787,7 @@ impl cbor_event::de::Deserialize for Block {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Consensus {
     pub slot_id: EpochSlotId,
     pub leader_key: hdwallet::XPub,
@@ -1,4 +1,5 @@
 use self::normal::BodyProof;
+use address::StakeholderId;
 use block::*;
 use cbor_event::{
     self,
@@ -7,9 +8,43 @@ use cbor_event::{
 };
 use config::ProtocolMagic;
 use hdwallet;
-use std::io::{BufRead, Write};
+use std::{
+    error, fmt,
+    io::{BufRead, Write},
+};
 use tags;
 
+/// Errors when verifying the signature of a main block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigError {
+    /// the signature does not match the header and the signing key
+    BadSignature,
+    /// the delegation certificate is not signed by its issuer
+    BadDelegationCert,
+    /// the issuer of the delegation certificate is not the slot leader
+    WrongIssuer,
+    /// the issuer delegated to itself
+    SelfSignedPSK,
+    /// lightweight delegation signatures are not supported
+    UnsupportedSignature,
+}
+impl fmt::Display for SigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SigError::BadSignature => write!(f, "invalid block signature"),
+            SigError::BadDelegationCert => write!(f, "invalid delegation certificate"),
+            SigError::WrongIssuer => {
+                write!(f, "delegation certificate issuer is not the block leader")
+            }
+            SigError::SelfSignedPSK => write!(f, "invalid self signing PSK"),
+            SigError::UnsupportedSignature => {
+                write!(f, "lightweight delegation signatures are not supported")
+            }
+        }
+    }
+}
+impl error::Error for SigError {}
+
 #[derive(Debug, Clone)]
 pub struct MainToSign<'a> {
     previous_header: &'a HeaderHash,
@@ -53,6 +88,33 @@ impl<'a> MainToSign<'a> {
     ) -> bool {
         verify_signature_with(protocol_magic, tag, proxy_sig, self)
     }
+
+    /// sign the header with the slot leader's key (no delegation)
+    pub fn sign(
+        &self,
+        protocol_magic: ProtocolMagic,
+        key: &hdwallet::XPrv,
+    ) -> hdwallet::Signature<SignData> {
+        key.sign(&self.data_to_sign(protocol_magic))
+    }
+
+    /// verify a signature created with [`sign`](#method.sign)
+    pub fn verify_sig(
+        &self,
+        protocol_magic: ProtocolMagic,
+        key: &hdwallet::XPub,
+        sig: &hdwallet::Signature<SignData>,
+    ) -> bool {
+        key.verify(&self.data_to_sign(protocol_magic), sig)
+    }
+
+    fn data_to_sign(&self, protocol_magic: ProtocolMagic) -> Vec<u8> {
+        tags::signing_payload(
+            tags::SigningTag::MainBlock,
+            protocol_magic,
+            &cbor!(self).unwrap(),
+        )
+    }
 }
 
 fn verify_signature_with<T>(
@@ -67,13 +129,11 @@ where
     let mut buf = vec!['0' as u8, '1' as u8];
 
     buf.extend(proxy_sig.psk.issuer_pk.as_ref());
-    buf.push(tag as u8);
-
-    se::Serializer::new(&mut buf)
-        .serialize(&protocol_magic)
-        .unwrap()
-        .serialize(data)
-        .unwrap();
+    buf.extend(tags::signing_payload(
+        tag,
+        protocol_magic,
+        &cbor!(data).unwrap(),
+    ));
 
     proxy_sig.psk.delegate_pk.verify(
         &buf,
@@ -85,7 +145,7 @@ type SignData = ();
 
 type ProxyCert = hdwallet::Signature<()>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProxySecretKey {
     pub omega: u64,
     pub issuer_pk: hdwallet::XPub,
@@ -133,6 +193,12 @@ impl ProxySecretKey {
         self.issuer_pk.verify(&buf, &self.cert)
     }
 
+    /// Verify that the certificate was issued by the given
+    /// stakeholder and that its signature is valid.
+    pub fn verify_issuer(&self, protocol_magic: ProtocolMagic, issuer: &StakeholderId) -> bool {
+        &StakeholderId::new(&self.issuer_pk) == issuer && self.verify(protocol_magic)
+    }
+
     /// Use 'issuer_prv' to sign 'delegate_pk' and 'omega' to create a
     /// ProxySecretKey.
     pub fn sign(
@@ -162,19 +228,13 @@ impl ProxySecretKey {
         buf2.extend(delegate_pk.as_ref());
         se::Serializer::new(&mut buf2).serialize(&omega).unwrap();
 
-        let mut buf = vec![];
-        buf.push(tags::SigningTag::ProxySK as u8);
-        se::Serializer::new(&mut buf)
-            .serialize(&protocol_magic)
-            .unwrap()
-            .write_bytes(buf2)
-            .unwrap();
-
-        buf
+        let mut body = se::Serializer::new_vec();
+        body.write_bytes(buf2).unwrap();
+        tags::signing_payload(tags::SigningTag::ProxySK, protocol_magic, &body.finalize())
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProxySignature {
     pub psk: ProxySecretKey,
     pub sig: hdwallet::Signature<()>,
@@ -203,7 +263,7 @@ impl cbor_event::de::Deserialize for ProxySignature {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BlockSignature {
     Signature(hdwallet::Signature<SignData>),
     ProxyLight(Vec<cbor_event::Value>), // TODO: decode
@@ -216,6 +276,15 @@ impl BlockSignature {
             _ => None,
         }
     }
+
+    /// the delegation certificate of a block signed by a delegate
+    /// (heavyweight delegation only)
+    pub fn delegation_cert(&self) -> Option<&ProxySecretKey> {
+        match self {
+            BlockSignature::ProxyHeavy(proxy_sig) => Some(&proxy_sig.psk),
+            _ => None,
+        }
+    }
 }
 impl cbor_event::se::Serialize for BlockSignature {
     fn serialize<'se, W: Write>(
@@ -167,10 +167,10 @@ impl Default for SoftwareVersion {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BlockHeaderAttributes(pub cbor_event::Value);
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct HeaderExtraData {
     pub block_version: BlockVersion,
     pub software_version: SoftwareVersion,
@@ -227,8 +227,19 @@ impl SscProof {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ChainDifficulty(u64);
+impl ChainDifficulty {
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// increase the difficulty by the given amount, returns `None`
+    /// if the result would overflow.
+    pub fn checked_add(self, difficulty: u64) -> Option<Self> {
+        self.0.checked_add(difficulty).map(ChainDifficulty)
+    }
+}
 
 impl fmt::Display for ChainDifficulty {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -244,7 +255,7 @@ impl From<u64> for ChainDifficulty {
 pub type EpochId = u64; // == EpochIndex
 pub type SlotId = u16; // == LocalSlotIndex
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "generic-serialization", derive(Serialize, Deserialize))]
 pub struct EpochSlotId {
     pub epoch: EpochId,
@@ -260,6 +271,39 @@ impl EpochSlotId {
     pub fn slot_number(&self) -> usize {
         (self.epoch as usize) * 21600 + (self.slotid as usize)
     }
+
+    /// the slot following this one, moving to the first slot of the
+    /// next epoch after the last slot of the epoch.
+    ///
+    /// returns `None` if `slots_per_epoch` is 0 or on overflow.
+    pub fn next_wrapping(&self, slots_per_epoch: SlotId) -> Option<Self> {
+        self.add(1, slots_per_epoch)
+    }
+
+    /// the slot `n` slots after this one, possibly in a later epoch.
+    ///
+    /// returns `None` if `slots_per_epoch` is 0 or on overflow.
+    pub fn add(&self, n: u32, slots_per_epoch: SlotId) -> Option<Self> {
+        if slots_per_epoch == 0 {
+            return None;
+        }
+        let slots_per_epoch = slots_per_epoch as u64;
+        let flat = self
+            .epoch
+            .checked_mul(slots_per_epoch)?
+            .checked_add(self.slotid as u64)?
+            .checked_add(n as u64)?;
+        Some(EpochSlotId {
+            epoch: flat / slots_per_epoch,
+            // the remainder is less than `slots_per_epoch`, a `SlotId`
+            slotid: (flat % slots_per_epoch) as SlotId,
+        })
+    }
+
+    /// the number of slots since the first slot of the epoch 0.
+    pub fn flat_index(&self, slots_per_epoch: SlotId) -> u64 {
+        self.epoch * slots_per_epoch as u64 + self.slotid as u64
+    }
 }
 impl fmt::Display for EpochSlotId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -576,7 +620,8 @@ impl cbor_event::de::Deserialize for SystemTag {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use hash::Blake2b256;
+    use hash::{self, Blake2b256};
+    use util::hex;
 
     #[test]
     fn debug_header_hash() {
@@ -586,4 +631,124 @@ mod tests {
             "HeaderHash(Blake2b256(0x89eb0d6a8a691dae2cd15ed0369931ce0a949ecafa5c3f93f8121833646e15c3))",
         );
     }
+
+    #[test]
+    fn header_hash_from_str() {
+        let s = "89eb0d6a8a691dae2cd15ed0369931ce0a949ecafa5c3f93f8121833646e15c3";
+        let h = HeaderHash::from_str(s).unwrap();
+        assert_eq!(h, HeaderHash(Blake2b256::new(&[0; 32])));
+        assert_eq!(format!("{}", h), s);
+    }
+
+    #[test]
+    fn header_hash_from_str_invalid() {
+        assert_eq!(
+            HeaderHash::from_str("89eb0d6a8a691dae").unwrap_err(),
+            hash::Error::InvalidHashSize(8, 32)
+        );
+        assert_eq!(
+            HeaderHash::from_str(&"z".repeat(64)).unwrap_err(),
+            hash::Error::HexadecimalError(hex::Error::UnknownSymbol(0))
+        );
+    }
+
+    #[test]
+    fn epoch_slot_id_wrapping() {
+        let slot = EpochSlotId {
+            epoch: 3,
+            slotid: 21598,
+        };
+        let next = slot.next_wrapping(21600).unwrap();
+        assert_eq!(
+            next,
+            EpochSlotId {
+                epoch: 3,
+                slotid: 21599
+            }
+        );
+        assert_eq!(
+            next.next_wrapping(21600),
+            Some(EpochSlotId {
+                epoch: 4,
+                slotid: 0
+            })
+        );
+        assert_eq!(next.flat_index(21600), 4 * 21600 - 1);
+        assert_eq!(next.flat_index(21600), next.slot_number() as u64);
+    }
+
+    #[test]
+    fn epoch_slot_id_add() {
+        let slot = EpochSlotId {
+            epoch: 0,
+            slotid: 10,
+        };
+        assert_eq!(slot.add(0, 21600), Some(slot));
+        assert_eq!(
+            slot.add(21590, 21600),
+            Some(EpochSlotId {
+                epoch: 1,
+                slotid: 0
+            })
+        );
+        assert_eq!(
+            slot.add(3 * 21600 + 5, 21600),
+            Some(EpochSlotId {
+                epoch: 3,
+                slotid: 15
+            })
+        );
+        assert_eq!(
+            slot.add(100_000, 21600).unwrap().flat_index(21600),
+            slot.flat_index(21600) + 100_000
+        );
+    }
+
+    #[test]
+    fn epoch_slot_id_add_invalid() {
+        let slot = EpochSlotId {
+            epoch: 0,
+            slotid: 10,
+        };
+        assert_eq!(slot.add(1, 0), None);
+        assert_eq!(slot.next_wrapping(0), None);
+
+        let last = EpochSlotId {
+            epoch: u64::max_value() / 21600,
+            slotid: 21599,
+        };
+        assert_eq!(last.add(u32::max_value(), 21600), None);
+
+        // the largest epoch length still fits in a `SlotId`
+        let slot = EpochSlotId {
+            epoch: 0,
+            slotid: SlotId::max_value() - 1,
+        };
+        assert_eq!(
+            slot.next_wrapping(SlotId::max_value()),
+            Some(EpochSlotId {
+                epoch: 1,
+                slotid: 0
+            })
+        );
+    }
+
+    #[test]
+    fn chain_difficulty_ordering() {
+        let a = ChainDifficulty::from(42);
+        let b = ChainDifficulty::from(43);
+        assert_eq!(a, ChainDifficulty::from(42));
+        assert_ne!(a, b);
+        assert!(a < b);
+        assert!(b > a);
+        assert_eq!(a.max(b), b);
+        assert_eq!(b.as_u64(), 43);
+    }
+
+    #[test]
+    fn chain_difficulty_checked_add() {
+        let a = ChainDifficulty::from(42);
+        assert_eq!(a.checked_add(1), Some(ChainDifficulty::from(43)));
+        assert_eq!(ChainDifficulty::from(::std::u64::MAX).checked_add(1), None);
+    }
 }
@@ -9,7 +9,7 @@ use std::{
     io::{BufRead, Write},
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct UpdatePayload {
     pub proposal: Option<UpdateProposal>,
     pub votes: Vec<UpdateVote>,
@@ -38,7 +38,7 @@ impl cbor_event::de::Deserialize for UpdatePayload {
 }
 
 /// Witness of delegation payload consisting of a simple hash
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct UpdateProof(Blake2b256);
 
 impl UpdateProof {
@@ -70,7 +70,7 @@ impl cbor_event::de::Deserialize for UpdateProof {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct UpdateProposal {
     pub block_version: types::BlockVersion,
     pub block_version_mod: BlockVersionModifier,
@@ -116,7 +116,7 @@ impl cbor_event::de::Deserialize for UpdateProposal {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BlockVersionModifier {
     pub script_version: Option<ScriptVersion>,
     pub slot_duration: Option<Millisecond>,
@@ -186,7 +186,7 @@ pub type Millisecond = u64;
 pub type FlatSlotId = u64;
 pub type TxFeePolicy = cbor_event::Value; // TODO
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UpdateData {
     pub app_diff_hash: hash::Blake2b256,
     pub pkg_hash: hash::Blake2b256,
@@ -220,7 +220,7 @@ impl cbor_event::de::Deserialize for UpdateData {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SoftforkRule {
     pub init_thd: types::CoinPortion,
     pub min_thd: types::CoinPortion,
@@ -247,7 +247,7 @@ impl cbor_event::de::Deserialize for SoftforkRule {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UpdateVote {
     pub key: hdwallet::XPub,
     pub proposal_id: UpId,
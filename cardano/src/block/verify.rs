@@ -1,9 +1,9 @@
 use self::normal::{BodyProof, VssCertificates};
-use self::sign::{BlockSignature, MainToSign};
+use self::sign::SigError;
 use self::update;
 use address;
 use block::*;
-use cbor_event::{self, se};
+use cbor_event;
 use coin;
 use config::ProtocolMagic;
 use fee;
@@ -19,7 +19,8 @@ use tx;
 #[derive(Debug)]
 pub enum Error {
     BadBlockSig,
-    BadTxWitness,
+    BadDelegationCert,
+    BadTxWitness(usize), // index of the failing input
     BadUpdateProposalSig,
     BadUpdateVoteSig,
     BadVssCertSig,
@@ -28,12 +29,14 @@ pub enum Error {
     DuplicateVSSKeys,
     EncodingError(cbor_event::Error),
     UnexpectedWitnesses,
+    UnsupportedBlockSig,
     MissingWitnesses,
     RedeemOutput,
     NoInputs,
     NoOutputs,
     SelfSignedPSK,
     WrongBlockHash,
+    WrongDelegationIssuer,
     WrongDelegationProof,
     WrongExtraDataProof,
     WrongBoundaryProof,
@@ -64,7 +67,8 @@ impl fmt::Display for Error {
         use self::Error::*;
         match self {
             BadBlockSig => write!(f, "invalid block signature"),
-            BadTxWitness => write!(f, "invalid transaction witness"),
+            BadDelegationCert => write!(f, "invalid delegation certificate"),
+            BadTxWitness(index) => write!(f, "invalid transaction witness for input {}", index),
             BadUpdateProposalSig => write!(f, "invalid update proposal signature"),
             BadUpdateVoteSig => write!(f, "invalid update vote signature"),
             BadVssCertSig => write!(f, "invalid VSS certificate signature"),
@@ -73,12 +77,16 @@ impl fmt::Display for Error {
             DuplicateVSSKeys => write!(f, "duplicated VSS keys"),
             EncodingError(_error) => write!(f, "encoding error"),
             UnexpectedWitnesses => write!(f, "transaction has more witnesses than inputs"),
+            UnsupportedBlockSig => write!(f, "lightweight delegation signatures are not supported"),
             MissingWitnesses => write!(f, "transaction has more inputs than witnesses"),
             RedeemOutput => write!(f, "invalid redeem output"),
             NoInputs => write!(f, "transaction has no inputs"),
             NoOutputs => write!(f, "transaction has no outputs"),
             SelfSignedPSK => write!(f, "invalid self signing PSK"),
             WrongBlockHash => write!(f, "block hash is invalid"),
+            WrongDelegationIssuer => {
+                write!(f, "delegation certificate issuer is not the block leader")
+            }
             WrongDelegationProof => write!(f, "delegation proof is invalid"),
             WrongExtraDataProof => write!(f, "extra data proof is invalid"),
             WrongBoundaryProof => write!(f, "boundary proof is invalid"),
@@ -114,12 +122,33 @@ impl fmt::Display for Error {
     }
 }
 
+impl From<SigError> for Error {
+    fn from(e: SigError) -> Self {
+        match e {
+            SigError::BadSignature => Error::BadBlockSig,
+            SigError::BadDelegationCert => Error::BadDelegationCert,
+            SigError::WrongIssuer => Error::WrongDelegationIssuer,
+            SigError::SelfSignedPSK => Error::SelfSignedPSK,
+            SigError::UnsupportedSignature => Error::UnsupportedBlockSig,
+        }
+    }
+}
+
 impl From<cbor_event::Error> for Error {
     fn from(e: cbor_event::Error) -> Self {
         Error::EncodingError(e)
     }
 }
 
+impl From<tx::WitnessCountError> for Error {
+    fn from(e: tx::WitnessCountError) -> Self {
+        match e {
+            tx::WitnessCountError::MissingWitnesses => Error::MissingWitnesses,
+            tx::WitnessCountError::UnexpectedWitnesses => Error::UnexpectedWitnesses,
+        }
+    }
+}
+
 impl error::Error for Error {
     fn cause(&self) -> Option<&error::Error> {
         match self {
@@ -130,6 +159,36 @@ impl error::Error for Error {
     }
 }
 
+/// Error returned by `verify_chain`, each variant contains the index
+/// of the first block breaking the chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError {
+    WrongPreviousBlock(usize, HeaderHash, HeaderHash), // index, actual, expected
+    BlockDateNotIncreasing(usize),
+    DifficultyNotIncreasing(usize),
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::ChainError::*;
+        match self {
+            WrongPreviousBlock(index, actual, expected) => write!(
+                f,
+                "block {} has parent {} while {} was expected",
+                index, actual, expected
+            ),
+            BlockDateNotIncreasing(index) => {
+                write!(f, "block {} is not later than its parent", index)
+            }
+            DifficultyNotIncreasing(index) => {
+                write!(f, "block {} has a lower difficulty than its parent", index)
+            }
+        }
+    }
+}
+
+impl error::Error for ChainError {}
+
 pub trait Verify {
     fn verify(&self, protocol_magic: ProtocolMagic) -> Result<(), Error>;
 }
@@ -152,6 +211,41 @@ pub fn verify_block(block_hash: &HeaderHash, blk: &Block) -> Result<(), Error> {
     Ok(())
 }
 
+/// Check that the given blocks form a chain: every block refers to
+/// the header hash of the block before it, block dates are strictly
+/// increasing and the chain difficulty increases with every main
+/// block (boundary blocks keep the difficulty of their parent).
+///
+/// This only checks the links between the blocks, not the blocks
+/// themselves (see `verify_block`) nor the first block's parent.
+pub fn verify_chain(blocks: &[Block]) -> Result<(), ChainError> {
+    for (index, pair) in blocks.windows(2).enumerate() {
+        let index = index + 1;
+        let (prev, blk) = (pair[0].header(), pair[1].header());
+
+        let expected = prev.compute_hash();
+        let actual = blk.previous_header();
+        if actual != expected {
+            return Err(ChainError::WrongPreviousBlock(index, actual, expected));
+        }
+
+        if blk.blockdate() <= prev.blockdate() {
+            return Err(ChainError::BlockDateNotIncreasing(index));
+        }
+
+        let difficulty_ok = if blk.is_boundary_block() {
+            blk.difficulty() >= prev.difficulty()
+        } else {
+            blk.difficulty() > prev.difficulty()
+        };
+        if !difficulty_ok {
+            return Err(ChainError::DifficultyNotIncreasing(index));
+        }
+    }
+
+    Ok(())
+}
+
 impl boundary::Block {
     fn verify(&self) -> Result<(), Error> {
         let hdr = &self.header;
@@ -212,27 +306,7 @@ impl normal::Block {
 
         // check consensus
         // FIXME: check slotid?
-        match &hdr.consensus.block_signature {
-            BlockSignature::Signature(_) => panic!("not implemented"),
-            BlockSignature::ProxyLight(_) => panic!("not implemented"),
-            BlockSignature::ProxyHeavy(proxy_sig) => {
-                // check against self-signed PSKs
-                if proxy_sig.psk.issuer_pk == proxy_sig.psk.delegate_pk {
-                    return Err(Error::SelfSignedPSK);
-                }
-
-                // verify the signature
-                let to_sign = MainToSign::from_header(&hdr);
-
-                if !to_sign.verify_proxy_sig(
-                    hdr.protocol_magic,
-                    tags::SigningTag::MainBlockHeavy,
-                    proxy_sig,
-                ) {
-                    return Err(Error::BadBlockSig);
-                }
-            }
-        }
+        self.verify_signature()?;
 
         Ok(())
     }
@@ -299,12 +373,15 @@ impl Verify for tx::TxAux {
             return Err(Error::MissingWitnesses);
         }
 
-        self.witness.iter().try_for_each(|in_witness| {
-            if !in_witness.verify_tx(protocol_magic, &self.tx) {
-                return Err(Error::BadTxWitness);
-            }
-            Ok(())
-        })?;
+        self.witness
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, in_witness)| {
+                if !in_witness.verify_tx(protocol_magic, &self.tx) {
+                    return Err(Error::BadTxWitness(index));
+                }
+                Ok(())
+            })?;
 
         // verify that txids of redeem inputs correspond to the redeem pubkey
         for (txin, in_witness) in self.tx.inputs.iter().zip(self.witness.iter()) {
@@ -335,13 +412,8 @@ impl Verify for VssCertificates {
 
         // verify every certificate's signature
         for vss_cert in self.iter() {
-            let mut buf = vec![];
-            buf.push(tags::SigningTag::VssCert as u8);
-            se::Serializer::new(&mut buf)
-                .serialize(&protocol_magic)?
-                .write_array(cbor_event::Len::Len(2))?
-                .serialize(&vss_cert.vss_key)?
-                .serialize(&vss_cert.expiry_epoch)?;
+            let body = cbor!(&(&vss_cert.vss_key, &vss_cert.expiry_epoch))?;
+            let buf = tags::signing_payload(tags::SigningTag::VssCert, protocol_magic, &body);
 
             if !vss_cert.signing_key.verify(
                 &buf,
@@ -366,8 +438,6 @@ impl Verify for update::UpdateProposal {
         // SystemTags are checked by SystemTag::new().
 
         // Check the signature on the update proposal.
-        let mut buf = vec![];
-
         let to_sign = update::UpdateProposalToSign {
             block_version: &self.block_version.clone(),
             block_version_mod: &self.block_version_mod.clone(),
@@ -376,11 +446,11 @@ impl Verify for update::UpdateProposal {
             attributes: &self.attributes.clone(),
         };
 
-        buf.push(tags::SigningTag::USProposal as u8);
-
-        se::Serializer::new(&mut buf)
-            .serialize(&protocol_magic)?
-            .serialize(&to_sign)?;
+        let buf = tags::signing_payload(
+            tags::SigningTag::USProposal,
+            protocol_magic,
+            &cbor!(&to_sign)?,
+        );
 
         if !self.from.verify(
             &buf,
@@ -395,14 +465,11 @@ impl Verify for update::UpdateProposal {
 
 impl Verify for update::UpdateVote {
     fn verify(&self, protocol_magic: ProtocolMagic) -> Result<(), Error> {
-        let mut buf = vec![];
-        se::Serializer::new(&mut buf)
-            .serialize(&(tags::SigningTag::USVote as u8))
-            .unwrap()
-            .serialize(&protocol_magic)
-            .unwrap()
-            .serialize(&(&self.proposal_id, &self.decision))
-            .unwrap();
+        let buf = tags::signing_payload(
+            tags::SigningTag::USVote,
+            protocol_magic,
+            &cbor!(&(&self.proposal_id, &self.decision))?,
+        );
 
         if !self.key.verify(
             &buf,
@@ -422,10 +489,14 @@ mod tests {
     use block::*;
     use cbor_event;
     use coin;
+    use config::ProtocolMagic;
+    use hdwallet;
     use merkle;
     use std::fmt::Debug;
     use std::mem;
     use std::str::FromStr;
+    use tx;
+    use util::hex;
 
     #[test]
     #[should_panic]
@@ -457,6 +528,316 @@ mod tests {
         coin::Coin::new(45_000_000_000_000_001).unwrap();
     }
 
+    #[test]
+    fn test_header_hash_and_previous_link() {
+        let blk = RawBlock(BLOCK1.to_vec()).decode().unwrap();
+        assert_eq!(
+            blk.header_hash(),
+            HeaderHash::from_str(&HEADER_HASH1).unwrap()
+        );
+
+        let prev = HeaderHash::from_str(&BLOCK1_PREVIOUS_HASH).unwrap();
+        assert!(blk.verify_prev(&prev));
+
+        let blk2 = RawBlock(BLOCK2.to_vec()).decode().unwrap();
+        assert!(!blk.verify_prev(&blk2.header_hash()));
+    }
+
+    #[test]
+    fn test_block_raw_roundtrip() {
+        for bytes in [BLOCK1, BLOCK2, BLOCK3].iter() {
+            let blk = RawBlock(bytes.to_vec()).decode().unwrap();
+            let raw = blk.to_raw();
+            // re-encoding a decoded block gives back the exact same bytes
+            assert_eq!(&raw.0[..], *bytes);
+
+            let decoded = raw.decode().unwrap();
+            assert_eq!(decoded.header_hash(), blk.header_hash());
+            assert_eq!(decoded.to_raw().0, raw.0);
+        }
+    }
+
+    #[test]
+    fn test_block_from_hex() {
+        let blk = Block::from_hex(&hex::encode(BLOCK2)).unwrap();
+        assert_eq!(blk, RawBlock(BLOCK2.to_vec()).decode().unwrap());
+
+        match Block::from_hex(&hex::encode(&BLOCK2[..BLOCK2.len() / 2])) {
+            Err(FromHexError::CborError(_)) => {}
+            res => panic!("expected a CBOR error, got {:?}", res),
+        }
+        match Block::from_hex("zz") {
+            Err(FromHexError::HexadecimalError(hex::Error::UnknownSymbol(0))) => {}
+            res => panic!("expected an hexadecimal error, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_block_eq() {
+        for bytes in [BLOCK1, BLOCK2, BLOCK3].iter() {
+            let blk1 = RawBlock(bytes.to_vec()).decode().unwrap();
+            let blk2 = RawBlock(bytes.to_vec()).decode().unwrap();
+            assert_eq!(blk1, blk2);
+            assert_eq!(
+                BlockHeader::from(blk1.header()),
+                BlockHeader::from(blk2.header())
+            );
+        }
+
+        let blk1 = RawBlock(BLOCK1.to_vec()).decode().unwrap();
+        let blk2 = RawBlock(BLOCK2.to_vec()).decode().unwrap();
+        assert_ne!(blk1, blk2);
+    }
+
+    #[test]
+    fn test_block_epoch() {
+        let blk = Block::BoundaryBlock(boundary::Block::new(
+            ProtocolMagic::default(),
+            HeaderHash::new(&[]),
+            vec![],
+            ChainDifficulty::from(0),
+        ));
+        assert!(blk.is_boundary_block());
+        assert_eq!(blk.epoch(), 0);
+
+        let mut mblk = main_block(BLOCK2);
+        mblk.header.consensus.slot_id = EpochSlotId {
+            epoch: 42,
+            slotid: 3,
+        };
+        let blk = Block::MainBlock(mblk);
+        assert!(!blk.is_boundary_block());
+        assert_eq!(blk.epoch(), 42);
+    }
+
+    #[test]
+    fn test_block_transactions() {
+        let mut mblk = main_block(BLOCK1);
+        let txids: Vec<_> = mblk.body.tx.iter().map(|txaux| txaux.tx.id()).collect();
+        mblk.body.tx.truncate(2);
+
+        let blk = Block::MainBlock(mblk);
+        assert_eq!(blk.transactions().len(), 2);
+        let found: Vec<_> = blk
+            .transactions()
+            .iter()
+            .map(|txaux| txaux.tx.id())
+            .collect();
+        assert_eq!(&found[..], &txids[..2]);
+
+        let blk = Block::BoundaryBlock(boundary::Block::new(
+            ProtocolMagic::default(),
+            HeaderHash::new(&[]),
+            vec![],
+            ChainDifficulty::from(0),
+        ));
+        assert!(blk.transactions().is_empty());
+    }
+
+    #[test]
+    fn test_block_output_addresses() {
+        let mut mblk = main_block(BLOCK1);
+        mblk.body.tx.truncate(2);
+        let outputs: Vec<_> = mblk
+            .body
+            .tx
+            .iter()
+            .flat_map(|txaux| txaux.tx.outputs.clone())
+            .collect();
+
+        let blk = Block::MainBlock(mblk);
+        let addresses = blk.output_addresses();
+        // both transactions have two outputs
+        assert_eq!(addresses.len(), 4);
+        for (address, output) in addresses.iter().zip(outputs.iter()) {
+            assert_eq!(*address, &output.address);
+        }
+    }
+
+    // a boundary block followed by two main blocks, only the fields
+    // checked by `verify_chain` are made consistent.
+    fn test_chain() -> Vec<Block> {
+        let mut chain = vec![Block::BoundaryBlock(boundary::Block::new(
+            ProtocolMagic::default(),
+            HeaderHash::new(&[]),
+            vec![],
+            ChainDifficulty::from(0),
+        ))];
+        for slotid in 0..2 {
+            let mut mblk = main_block(BLOCK2);
+            mblk.header.previous_header = chain.last().unwrap().header_hash();
+            mblk.header.consensus.slot_id = EpochSlotId { epoch: 0, slotid };
+            mblk.header.consensus.chain_difficulty = ChainDifficulty::from(slotid as u64 + 1);
+            chain.push(Block::MainBlock(mblk));
+        }
+        chain
+    }
+
+    #[test]
+    fn test_verify_chain() {
+        let chain = test_chain();
+        assert_eq!(verify_chain(&chain), Ok(()));
+        assert_eq!(verify_chain(&chain[..1]), Ok(()));
+        assert_eq!(verify_chain(&[]), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_chain_broken_link() {
+        let mut chain = test_chain();
+        let expected = chain[1].header_hash();
+        let actual = chain[0].header_hash();
+        if let Block::MainBlock(ref mut mblk) = chain[2] {
+            mblk.header.previous_header = actual.clone();
+        }
+        assert_eq!(
+            verify_chain(&chain),
+            Err(ChainError::WrongPreviousBlock(2, actual, expected))
+        );
+    }
+
+    #[test]
+    fn test_verify_chain_not_increasing() {
+        let mut chain = test_chain();
+        if let Block::MainBlock(ref mut mblk) = chain[2] {
+            mblk.header.consensus.slot_id.slotid = 0;
+        }
+        assert_eq!(
+            verify_chain(&chain),
+            Err(ChainError::BlockDateNotIncreasing(2))
+        );
+
+        let mut chain = test_chain();
+        if let Block::MainBlock(ref mut mblk) = chain[1] {
+            mblk.header.consensus.chain_difficulty = ChainDifficulty::from(0);
+        }
+        assert_eq!(
+            verify_chain(&chain[..2]),
+            Err(ChainError::DifficultyNotIncreasing(1))
+        );
+    }
+
+    #[test]
+    fn test_tx_id() {
+        let blk = RawBlock(BLOCK1.to_vec()).decode().unwrap();
+        match blk {
+            Block::MainBlock(mblk) => assert_eq!(
+                mblk.body.tx[0].tx.id(),
+                tx::TxId::from_str(
+                    "e2b7cb57755dbead34f2964e622be273ff89164dbecb1629216a0166a54557b5"
+                )
+                .unwrap()
+            ),
+            Block::BoundaryBlock(_) => panic!("expected a main block"),
+        }
+    }
+
+    fn main_block(bytes: &[u8]) -> normal::Block {
+        match RawBlock(bytes.to_vec()).decode().unwrap() {
+            Block::MainBlock(mblk) => mblk,
+            Block::BoundaryBlock(_) => panic!("expected a main block"),
+        }
+    }
+
+    #[test]
+    fn test_proxy_block_signature() {
+        let blk = main_block(BLOCK1);
+        assert_eq!(blk.verify_signature(), Ok(()));
+
+        // the signature does not cover the tampered header
+        let mut tampered = blk.clone();
+        tampered.header.consensus.chain_difficulty = ChainDifficulty::from(0);
+        assert_eq!(
+            tampered.verify_signature(),
+            Err(sign::SigError::BadSignature)
+        );
+
+        // the delegation certificate does not cover the tampered omega
+        let mut tampered = blk.clone();
+        if let sign::BlockSignature::ProxyHeavy(proxy_sig) =
+            &mut tampered.header.consensus.block_signature
+        {
+            proxy_sig.psk.omega += 1;
+        }
+        assert_eq!(
+            tampered.verify_signature(),
+            Err(sign::SigError::BadDelegationCert)
+        );
+
+        // the slot leader did not issue the delegation certificate
+        let mut tampered = blk.clone();
+        tampered.header.consensus.leader_key = hdwallet::XPub::from_bytes([0; hdwallet::XPUB_SIZE]);
+        assert_eq!(
+            tampered.verify_signature(),
+            Err(sign::SigError::WrongIssuer)
+        );
+    }
+
+    #[test]
+    fn test_delegation_cert() {
+        let blk = main_block(BLOCK1);
+        let consensus = &blk.header.consensus;
+        let psk = consensus.block_signature.delegation_cert().unwrap();
+
+        // the block was produced by a delegate of the slot leader
+        assert_eq!(psk.issuer_pk, consensus.leader_key);
+        assert_ne!(psk.delegate_pk, consensus.leader_key);
+
+        let pm = blk.header.protocol_magic;
+        let leader = address::StakeholderId::new(&consensus.leader_key);
+        assert!(psk.verify_issuer(pm, &leader));
+        let other = address::StakeholderId::new(&psk.delegate_pk);
+        assert!(!psk.verify_issuer(pm, &other));
+
+        let sig = sign::BlockSignature::Signature(hdwallet::Signature::from_bytes(
+            [0; hdwallet::SIGNATURE_SIZE],
+        ));
+        assert!(sig.delegation_cert().is_none());
+    }
+
+    #[test]
+    fn test_block_signature() {
+        let seed = hdwallet::Seed::from_bytes([0; hdwallet::SEED_SIZE]);
+        let leader = hdwallet::XPrv::generate_from_seed(&seed);
+
+        let mut blk = main_block(BLOCK1);
+        blk.header.consensus.leader_key = leader.public();
+        let sig =
+            sign::MainToSign::from_header(&blk.header).sign(blk.header.protocol_magic, &leader);
+        blk.header.consensus.block_signature = sign::BlockSignature::Signature(sig);
+        assert_eq!(blk.verify_signature(), Ok(()));
+
+        let mut tampered = blk.clone();
+        tampered.header.previous_header = HeaderHash::new(b"not the previous block");
+        assert_eq!(
+            tampered.verify_signature(),
+            Err(sign::SigError::BadSignature)
+        );
+
+        let other_seed = hdwallet::Seed::from_bytes([1; hdwallet::SEED_SIZE]);
+        let mut tampered = blk.clone();
+        tampered.header.consensus.leader_key =
+            hdwallet::XPrv::generate_from_seed(&other_seed).public();
+        assert_eq!(
+            tampered.verify_signature(),
+            Err(sign::SigError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn test_body_proof() {
+        let blk = RawBlock(BLOCK1.to_vec()).decode().unwrap();
+        assert!(blk.verify_body_proof());
+        if let Block::MainBlock(mblk) = &blk {
+            assert_eq!(mblk.body.compute_tx_proof(), mblk.header.body_proof.tx);
+        }
+
+        let mut blk = blk.clone();
+        if let Block::MainBlock(mblk) = &mut blk {
+            mblk.body.tx[0].tx.outputs[0].value = coin::Coin::new(123).unwrap();
+        }
+        assert!(!blk.verify_body_proof());
+    }
+
     fn expect_error<T, Error>(res: &Result<T, Error>, expected: Error)
     where
         Error: Debug,
@@ -515,7 +896,19 @@ mod tests {
             if let Block::MainBlock(mblk) = &mut blk {
                 mblk.body.tx[0].tx.outputs[0].value = coin::Coin::new(123).unwrap();
             }
-            expect_error(&verify_block(&hash, &blk), Error::BadTxWitness);
+            expect_error(&verify_block(&hash, &blk), Error::BadTxWitness(0));
+        }
+
+        // verify a transaction witness against another network
+        {
+            if let Block::MainBlock(mblk) = &blk {
+                let txaux = &mblk.body.tx[0];
+                assert!(txaux.verify(mblk.header.protocol_magic).is_ok());
+                match txaux.verify(ProtocolMagic::from(1097911063)) {
+                    Err(Error::BadTxWitness(index)) => assert_eq!(index, 0),
+                    res => panic!("Expected a bad witness on input 0 but got {:?}", res),
+                }
+            }
         }
 
         // create a zero output
@@ -610,6 +1003,51 @@ mod tests {
             expect_error(&verify_block(&hash, &blk), Error::BadBlockSig);
         }
 
+        // invalidate the delegation certificate
+        {
+            let mut blk = blk.clone();
+            if let Block::MainBlock(mblk) = &mut blk {
+                if let sign::BlockSignature::ProxyHeavy(proxy_sig) =
+                    &mut mblk.header.consensus.block_signature
+                {
+                    proxy_sig.psk.omega += 1;
+                }
+            }
+            expect_error(&verify_block(&hash, &blk), Error::BadDelegationCert);
+        }
+
+        // delegation certificate not issued by the slot leader
+        {
+            let mut blk = blk.clone();
+            if let Block::MainBlock(mblk) = &mut blk {
+                mblk.header.consensus.leader_key =
+                    hdwallet::XPub::from_bytes([0; hdwallet::XPUB_SIZE]);
+            }
+            expect_error(&verify_block(&hash, &blk), Error::WrongDelegationIssuer);
+        }
+
+        // self signed delegation certificate
+        {
+            let mut blk = blk.clone();
+            if let Block::MainBlock(mblk) = &mut blk {
+                if let sign::BlockSignature::ProxyHeavy(proxy_sig) =
+                    &mut mblk.header.consensus.block_signature
+                {
+                    proxy_sig.psk.delegate_pk = proxy_sig.psk.issuer_pk.clone();
+                }
+            }
+            expect_error(&verify_block(&hash, &blk), Error::SelfSignedPSK);
+        }
+
+        // lightweight delegation signature
+        {
+            let mut blk = blk.clone();
+            if let Block::MainBlock(mblk) = &mut blk {
+                mblk.header.consensus.block_signature = sign::BlockSignature::ProxyLight(vec![]);
+            }
+            expect_error(&verify_block(&hash, &blk), Error::UnsupportedBlockSig);
+        }
+
         // invalidate a VSS certificate
         {
             let mut blk = blk3.clone();
@@ -713,6 +1151,8 @@ mod tests {
 
     // a block with 6 transactions
     const HEADER_HASH1: &str = "ae443ffffe52cc29de83312d2819b3955fc306ce65ae6aa5b26f1d3c76e91842";
+    const BLOCK1_PREVIOUS_HASH: &str =
+        "8f22a769b6964220ff0a5186175beaa65fa301a42009b6c43207546b37a90708";
     const BLOCK1: &'static [u8] = &[
         130, 1, 131, 133, 26, 37, 192, 15, 169, 88, 32, 143, 34, 167, 105, 182, 150, 66, 32, 255,
         10, 81, 134, 23, 91, 234, 166, 95, 163, 1, 164, 32, 9, 182, 196, 50, 7, 84, 107, 55, 169,
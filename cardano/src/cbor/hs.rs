@@ -4,7 +4,7 @@ pub mod util {
     //! CBor util and other stuff
 
     use cbor_event::{self, de::Deserializer, se::Serializer, Len};
-    use crc32::crc32;
+    use util::crc32::crc32;
 
     pub fn encode_with_crc32_<T, W>(t: &T, s: &mut Serializer<W>) -> cbor_event::Result<()>
     where
@@ -19,11 +19,21 @@ pub mod util {
             .write_unsigned_integer(crc32 as u64)?;
         Ok(())
     }
-    pub fn raw_with_crc32<R: std::io::BufRead>(
+    /// decode the `[tag(24, bytes), crc32]` envelope, returning the
+    /// embedded bytes and the announced crc32 **without** checking it.
+    ///
+    /// Use `raw_with_crc32` unless the caller needs to report a checksum
+    /// mismatch itself.
+    pub fn raw_and_crc32<R: std::io::BufRead>(
         raw: &mut Deserializer<R>,
-    ) -> cbor_event::Result<Vec<u8>> {
+    ) -> cbor_event::Result<(Vec<u8>, u32)> {
         let len = raw.array()?;
-        assert!(len == Len::Len(2));
+        if len != Len::Len(2) {
+            return Err(cbor_event::Error::CustomError(format!(
+                "Invalid CRC32 envelope: {:?} but expected an array of 2 items",
+                len
+            )));
+        }
 
         let tag = raw.tag()?;
         if tag != 24 {
@@ -35,10 +45,24 @@ pub mod util {
         let bytes = raw.bytes()?;
 
         let crc = raw.unsigned_integer()?;
+        if crc > u64::from(u32::max_value()) {
+            return Err(cbor_event::Error::CustomError(format!(
+                "Invalid CRC32: 0x{:x} does not fit in 32 bits",
+                crc
+            )));
+        }
+
+        Ok((bytes, crc as u32))
+    }
+
+    pub fn raw_with_crc32<R: std::io::BufRead>(
+        raw: &mut Deserializer<R>,
+    ) -> cbor_event::Result<Vec<u8>> {
+        let (bytes, crc) = raw_and_crc32(raw)?;
 
         let found_crc = crc32(&bytes);
 
-        if crc != found_crc as u64 {
+        if crc != found_crc {
             return Err(cbor_event::Error::CustomError(format!(
                 "Invalid CRC32: 0x{:x} but expected 0x{:x}",
                 crc, found_crc
@@ -1 +1,43 @@
+use cbor_event::se::{Serialize, Serializer};
+use std::io::{self, Write};
+
 pub mod hs;
+
+// a writer only counting the bytes written to it
+struct Counter(usize);
+impl Write for Counter {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.0 += bytes.len();
+        Ok(bytes.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// compute the size of the CBOR encoding of the given value without
+/// allocating the encoded bytes.
+pub fn encoded_len<T: Serialize>(value: &T) -> usize {
+    let mut se = Serializer::new(Counter(0));
+    value.serialize(&mut se).expect("serialize to a counter");
+    se.finalize().0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tx::{Tx, TxId, TxoPointer};
+
+    #[test]
+    fn encoded_len_of_tx() {
+        let tx = Tx::new_with(
+            vec![
+                TxoPointer::new(TxId::new(&[0; 32]), 0),
+                TxoPointer::new(TxId::new(&[1; 32]), 666),
+            ],
+            vec![],
+        );
+        assert_eq!(encoded_len(&tx), cbor!(&tx).unwrap().len());
+        assert_eq!(encoded_len(&42u64), 2);
+    }
+}
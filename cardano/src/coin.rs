@@ -105,6 +105,37 @@ impl Coin {
         }
     }
 
+    /// add the two coins, failing if the result is beyond `MAX_COIN`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cardano::coin::{Coin, MAX_COIN};
+    ///
+    /// let max = Coin::new(MAX_COIN).unwrap();
+    ///
+    /// assert!(Coin::unit().checked_add(Coin::unit()).is_ok());
+    /// assert!(max.checked_add(Coin::unit()).is_err());
+    /// ```
+    pub fn checked_add(self, other: Self) -> Result<Self> {
+        self + other
+    }
+
+    /// subtract `other` from the coin, failing if the result would
+    /// be negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cardano::coin::{Coin, Error};
+    ///
+    /// assert_eq!(Coin::unit().checked_sub(Coin::unit()), Ok(Coin::zero()));
+    /// assert_eq!(Coin::zero().checked_sub(Coin::unit()), Err(Error::Negative));
+    /// ```
+    pub fn checked_sub(self, other: Self) -> Result<Self> {
+        self - other
+    }
+
     pub fn differential(self, against: Self) -> CoinDiff {
         match self.0.cmp(&against.0) {
             Ordering::Equal => CoinDiff::Zero,
@@ -227,4 +258,33 @@ mod test {
             *coin == coin2
         }
     }
+
+    #[test]
+    fn coin_bounds() {
+        assert_eq!(Coin::new(0), Ok(Coin::zero()));
+        assert!(Coin::new(MAX_COIN).is_ok());
+        assert_eq!(
+            Coin::new(MAX_COIN + 1),
+            Err(Error::OutOfBound(MAX_COIN + 1))
+        );
+    }
+
+    #[test]
+    fn coin_checked_add() {
+        let max = Coin::new(MAX_COIN).unwrap();
+        assert_eq!(Coin::zero().checked_add(Coin::zero()), Ok(Coin::zero()));
+        assert_eq!(max.checked_add(Coin::zero()), Ok(max));
+        assert_eq!(
+            max.checked_add(Coin::unit()),
+            Err(Error::OutOfBound(MAX_COIN + 1))
+        );
+    }
+
+    #[test]
+    fn coin_checked_sub() {
+        let max = Coin::new(MAX_COIN).unwrap();
+        assert_eq!(max.checked_sub(max), Ok(Coin::zero()));
+        assert_eq!(Coin::zero().checked_sub(Coin::zero()), Ok(Coin::zero()));
+        assert_eq!(Coin::zero().checked_sub(Coin::unit()), Err(Error::Negative));
+    }
 }
@@ -143,6 +143,19 @@ pub struct GenesisData {
     pub boot_stakeholders: BTreeMap<address::StakeholderId, BootStakeholder>,
 }
 
+impl GenesisData {
+    /// total of the initial AVVM and non-AVVM balances, failing if
+    /// the sum is greater than the maximum amount of coins.
+    pub fn total_stake(&self) -> coin::Result<coin::Coin> {
+        coin::sum_coins(
+            self.avvm_distr
+                .values()
+                .chain(self.non_avvm_balances.values())
+                .cloned(),
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BootStakeholder {
     pub weight: BootStakeWeight,
@@ -152,3 +165,54 @@ pub struct BootStakeholder {
 }
 
 pub type BootStakeWeight = u16;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genesis_data(avvm: &[u64], non_avvm: &[u64]) -> GenesisData {
+        let avvm_distr = avvm
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let pk = redeem::PublicKey::from_bytes([i as u8; redeem::PUBLICKEY_SIZE]);
+                (pk, coin::Coin::new(*v).unwrap())
+            })
+            .collect();
+        let non_avvm_balances = non_avvm
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let pk = hdwallet::XPub::from_bytes([i as u8; hdwallet::XPUB_SIZE]);
+                let addr = address::ExtendedAddr::new_simple(pk, NetworkMagic::NoMagic);
+                (addr.to_address(), coin::Coin::new(*v).unwrap())
+            })
+            .collect();
+        GenesisData {
+            genesis_prev: block::HeaderHash::new(b"genesis"),
+            epoch_stability_depth: 2160,
+            start_time: SystemTime::UNIX_EPOCH,
+            slot_duration: Duration::from_secs(20),
+            protocol_magic: ProtocolMagic::default(),
+            fee_policy: fee::LinearFee::default(),
+            avvm_distr,
+            non_avvm_balances,
+            boot_stakeholders: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn genesis_total_stake() {
+        let genesis = genesis_data(&[1_000, 2_000], &[30, 4]);
+        assert_eq!(genesis.total_stake(), coin::Coin::new(3_034));
+
+        let genesis = genesis_data(&[], &[]);
+        assert_eq!(genesis.total_stake(), Ok(coin::Coin::zero()));
+    }
+
+    #[test]
+    fn genesis_total_stake_overflow() {
+        let genesis = genesis_data(&[coin::MAX_COIN], &[1]);
+        assert!(genesis.total_stake().is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! Fee calculation and fee algorithms
 
+use cbor;
 use cbor_event;
 use coin;
 use coin::Coin;
@@ -149,10 +150,7 @@ impl FeeAlgorithm for LinearFee {
     }
 
     fn calculate_for_txaux(&self, txaux: &TxAux) -> Result<Fee> {
-        // the only reason the cbor serialisation would fail is if there was
-        // no more memory free to allocate.
-        let txbytes = cbor!(txaux)?;
-        self.estimate(txbytes.len())
+        self.estimate(cbor::encoded_len(txaux))
     }
     fn calculate_for_txaux_component(&self, tx: &Tx, witnesses: &Vec<TxInWitness>) -> Result<Fee> {
         let size_bytes = txaux_serialize_size(tx, witnesses);
@@ -169,6 +167,11 @@ impl Default for LinearFee {
 #[cfg(test)]
 mod test {
     use super::*;
+    use address::ExtendedAddr;
+    use config::ProtocolMagic;
+    use hdwallet;
+    use std::str::FromStr;
+    use tx::{TxId, TxOut, TxWitness, TxoPointer};
 
     fn test_milli_add_eq(v1: u64, v2: u64) {
         let v = v1 + v2;
@@ -201,4 +204,52 @@ mod test {
         test_milli_mul_eq(241, 900001_900);
         test_milli_mul_eq(241, 400);
     }
+
+    #[test]
+    fn check_mainnet_linear_fee() {
+        // 155381 + 43.946 * bytes, rounded up to the next lovelace
+        let fee = LinearFee::default();
+        assert_eq!(
+            fee.estimate(0).unwrap().to_coin(),
+            Coin::new(155381).unwrap()
+        );
+        assert_eq!(
+            fee.estimate(200).unwrap().to_coin(),
+            Coin::new(164171).unwrap()
+        );
+        assert_eq!(
+            fee.estimate(1000).unwrap().to_coin(),
+            Coin::new(199327).unwrap()
+        );
+    }
+
+    #[test]
+    fn check_mainnet_fee_of_txaux() {
+        // one input, one output to a mainnet address and a public key
+        // witness: 238 bytes once encoded, so a fee of
+        // 155381 + 43.946 * 238 = 165840.148 lovelaces, rounded up
+        let seed = hdwallet::Seed::from_bytes([0; hdwallet::SEED_SIZE]);
+        let key = hdwallet::XPrv::generate_from_seed(&seed);
+        let address =
+            ExtendedAddr::from_str("Ae2tdPwUPEZ81gMkWH2PgB55y18pp2hxDxM2cmzBNnQtyLhJHqUp622zVgz")
+                .unwrap();
+        let tx = Tx::new_with(
+            vec![TxoPointer::new(TxId::new(&[0; 32]), 0)],
+            vec![TxOut::new(address, Coin::new(1_000_000).unwrap())],
+        );
+        let witness = TxInWitness::new(ProtocolMagic::default(), &key, &tx.id());
+        let txaux = TxAux::new(tx, TxWitness::from(vec![witness]));
+        assert_eq!(cbor::encoded_len(&txaux), 238);
+
+        let fee = LinearFee::default();
+        assert_eq!(
+            fee.calculate_for_txaux(&txaux).unwrap().to_coin(),
+            Coin::new(165841).unwrap()
+        );
+        assert_eq!(
+            fee.calculate_for_txaux_component(&txaux.tx, &txaux.witness)
+                .unwrap(),
+            fee.calculate_for_txaux(&txaux).unwrap()
+        );
+    }
 }
@@ -55,14 +55,37 @@ pub type Result<T> = result::Result<T, Error>;
 
 /// defines a blake2b object
 macro_rules! define_blake2b_new {
-    ($hash_ty:ty) => {
+    ($hash_ty:ty, $context_ty:ident) => {
         impl $hash_ty {
             pub fn new(buf: &[u8]) -> Self {
-                let mut b2b = Blake2b::new(Self::HASH_SIZE);
-                let mut out = [0; Self::HASH_SIZE];
-                b2b.input(buf);
-                b2b.result(&mut out);
-                Self::from(out)
+                let mut context = $context_ty::new();
+                context.update(buf);
+                context.finalize()
+            }
+        }
+
+        /// incremental blake2b hashing, for when the data to hash is not
+        /// available as one contiguous slice (e.g. streamed from disk).
+        pub struct $context_ty(Blake2b);
+        impl $context_ty {
+            pub fn new() -> Self {
+                $context_ty(Blake2b::new(<$hash_ty>::HASH_SIZE))
+            }
+
+            pub fn update(&mut self, buf: &[u8]) -> &mut Self {
+                self.0.input(buf);
+                self
+            }
+
+            pub fn finalize(mut self) -> $hash_ty {
+                let mut out = [0; <$hash_ty>::HASH_SIZE];
+                self.0.result(&mut out);
+                <$hash_ty>::from(out)
+            }
+        }
+        impl Default for $context_ty {
+            fn default() -> Self {
+                $context_ty::new()
             }
         }
     };
@@ -223,14 +246,16 @@ pub const HASH_SIZE_256: usize = 32;
 #[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
 pub struct Blake2b224([u8; HASH_SIZE_224]);
 define_hash_object!(Blake2b224, Blake2b224, HASH_SIZE_224);
-define_blake2b_new!(Blake2b224);
+define_blake2b_new!(Blake2b224, Blake2b224Context);
 
 /// Blake2b 256 bits
 #[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
 pub struct Blake2b256([u8; HASH_SIZE_256]);
 define_hash_object!(Blake2b256, Blake2b256, HASH_SIZE_256);
-define_blake2b_new!(Blake2b256);
+define_blake2b_new!(Blake2b256, Blake2b256Context);
 
+/// SHA3 256 bits, used (with Blake2b224) to hash the spending data of
+/// the addresses
 #[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
 pub struct Sha3_256([u8; HASH_SIZE_256]);
 define_hash_object!(Sha3_256, Sha3_256, HASH_SIZE_256);
@@ -259,6 +284,30 @@ mod test {
         assert!(cbor_event::test_encode_decode(&Blake2b256::new([0; 256].as_ref())).unwrap())
     }
 
+    #[test]
+    fn blake2b_256_incremental() {
+        let data: Vec<u8> = (0..1024u32).map(|i| i as u8).collect();
+
+        let mut context = Blake2b256Context::default();
+        for chunk in data.chunks(100) {
+            context.update(chunk);
+        }
+
+        assert_eq!(context.finalize(), Blake2b256::new(&data));
+    }
+
+    #[test]
+    fn sha3_256() {
+        assert_eq!(
+            format!("{}", Sha3_256::new(b"")),
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        );
+        assert_eq!(
+            format!("{}", Sha3_256::new(b"abc")),
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+    }
+
     #[test]
     fn debug_blake2b_224() {
         let h = Blake2b224::new([0; 28].as_ref());
@@ -337,6 +337,21 @@ mod tests {
         assert_eq!(path, key.decrypt_path(&payload).unwrap())
     }
 
+    #[test]
+    fn hdpayload_other_wallet() {
+        let path = Path::new(vec![0x80000000, 42]);
+        let seed = hdwallet::Seed::from_bytes([0; hdwallet::SEED_SIZE]);
+        let key = HDKey::new(&hdwallet::XPrv::generate_from_seed(&seed).public());
+        let seed = hdwallet::Seed::from_bytes([1; hdwallet::SEED_SIZE]);
+        let other_key = HDKey::new(&hdwallet::XPrv::generate_from_seed(&seed).public());
+
+        let payload = key.encrypt_path(&path);
+        match other_key.decrypt_path(&payload).unwrap_err() {
+            Error::CannotDecrypt => {}
+            err => panic!("expecting Error::CannotDecrypt but got {:#?}", err),
+        }
+    }
+
     #[test]
     fn unit1() {
         let key = HDKey::from_bytes([0u8; 32]);
@@ -26,7 +26,7 @@ use std::{
     io::{BufRead, Write},
     result,
 };
-use util::{hex, securemem};
+use util::{hex, secret::Secret, securemem};
 
 use cbor_event::{self, de::Deserializer, se::Serializer};
 
@@ -68,6 +68,8 @@ pub enum Error {
     HexadecimalError(hex::Error),
     ExpectedSoftDerivation,
     InvalidDerivation,
+    /// the given string is not a valid derivation path (see `DerivationPath`)
+    InvalidDerivationPath(&'static str),
 }
 
 impl fmt::Display for Error {
@@ -97,6 +99,7 @@ impl fmt::Display for Error {
             &Error::HexadecimalError(_) => write!(f, "Invalid hexadecimal."),
             &Error::ExpectedSoftDerivation => write!(f, "expected soft derivation"),
             &Error::InvalidDerivation => write!(f, "invalid derivation"),
+            &Error::InvalidDerivationPath(ref err) => write!(f, "invalid derivation path: {}", err),
         }
     }
 }
@@ -190,7 +193,7 @@ impl Drop for Seed {
 /// HDWallet extended private key
 ///
 /// Effectively this is ed25519 extended secret key (64 bytes) followed by a chain code (32 bytes)
-pub struct XPrv([u8; XPRV_SIZE]);
+pub struct XPrv(Secret<[u8; XPRV_SIZE]>);
 impl XPrv {
     /// create the Root private key `XPrv` of the HDWallet associated to this `Seed`
     ///
@@ -261,7 +264,7 @@ impl XPrv {
     // This function does not perform any validity check and should not be used outside
     // of this module.
     fn from_bytes(bytes: [u8; XPRV_SIZE]) -> Self {
-        XPrv(bytes)
+        XPrv(Secret::new(bytes))
     }
 
     /// Create a `XPrv` by taking ownership of the given array
@@ -280,7 +283,7 @@ impl XPrv {
             return Err(Error::InvalidXPrv("expected 3 lowest bits to be 0b000"));
         }
 
-        Ok(XPrv(bytes))
+        Ok(XPrv(Secret::new(bytes)))
     }
 
     /// Create a `XPrv` from the given slice. This slice must be of size `XPRV_SIZE`
@@ -346,6 +349,21 @@ impl XPrv {
     pub fn derive(&self, scheme: DerivationScheme, index: DerivationIndex) -> Self {
         derive_private(self, index, scheme)
     }
+
+    /// derive every index of the given `DerivationPath` in turn
+    ///
+    /// ```
+    /// use cardano::hdwallet::{DerivationPath, DerivationScheme, Seed, XPrv};
+    ///
+    /// let xprv = XPrv::generate_from_seed(&Seed::from_bytes([0; 32]));
+    /// let path = "m/44'/1815'/0'/0/3".parse::<DerivationPath>().unwrap();
+    ///
+    /// let child = xprv.derive_path(DerivationScheme::V2, &path);
+    /// ```
+    pub fn derive_path(&self, scheme: DerivationScheme, path: &DerivationPath) -> Self {
+        path.iter()
+            .fold(self.clone(), |xprv, index| xprv.derive(scheme, *index))
+    }
 }
 impl PartialEq for XPrv {
     fn eq(&self, rhs: &XPrv) -> bool {
@@ -360,7 +378,7 @@ impl Clone for XPrv {
 }
 impl fmt::Debug for XPrv {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", hex::encode(self.as_ref()))
+        write!(f, "XPrv({:?})", self.0)
     }
 }
 impl fmt::Display for XPrv {
@@ -376,12 +394,12 @@ impl ::std::str::FromStr for XPrv {
 }
 impl AsRef<[u8]> for XPrv {
     fn as_ref(&self) -> &[u8] {
-        &self.0
+        self.0.expose()
     }
 }
 impl Drop for XPrv {
     fn drop(&mut self) {
-        securemem::zero(&mut self.0);
+        securemem::zero(self.0.expose_mut());
     }
 }
 #[cfg(feature = "generic-serialization")]
@@ -772,6 +790,78 @@ pub type ChainCode = [u8; CHAIN_CODE_SIZE];
 
 pub type DerivationIndex = u32;
 
+/// A derivation path from a root key, as written in the usual
+/// `m/44'/1815'/0'/0/3` notation: the `m` stands for the root key and
+/// a trailing `'` marks a hardened index (offset by `0x80000000`).
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[cfg_attr(feature = "generic-serialization", derive(Serialize, Deserialize))]
+pub struct DerivationPath(Vec<DerivationIndex>);
+impl DerivationPath {
+    pub fn new(indices: Vec<DerivationIndex>) -> Self {
+        DerivationPath(indices)
+    }
+}
+impl ::std::ops::Deref for DerivationPath {
+    type Target = [DerivationIndex];
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl AsRef<[DerivationIndex]> for DerivationPath {
+    fn as_ref(&self) -> &[DerivationIndex] {
+        self.0.as_ref()
+    }
+}
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in self.0.iter() {
+            match to_type(*index) {
+                DerivationType::Soft(i) => write!(f, "/{}", i)?,
+                DerivationType::Hard(i) => write!(f, "/{}'", i & 0x7fffffff)?,
+            }
+        }
+        Ok(())
+    }
+}
+impl ::std::str::FromStr for DerivationPath {
+    type Err = Error;
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let mut components = s.split('/');
+
+        if components.next() != Some("m") {
+            return Err(Error::InvalidDerivationPath(
+                "expected the path to start with `m`",
+            ));
+        }
+
+        let mut indices = Vec::new();
+        for component in components {
+            let (digits, hardened) = if component.ends_with('\'') {
+                (&component[..component.len() - 1], true)
+            } else {
+                (component, false)
+            };
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(Error::InvalidDerivationPath(
+                    "expected a decimal index, optionally followed by an apostrophe",
+                ));
+            }
+            let index = match digits.parse::<DerivationIndex>() {
+                Ok(index) if index < 0x80000000 => index,
+                _ => {
+                    return Err(Error::InvalidDerivationPath(
+                        "index out of bound, expected an index below 0x80000000",
+                    ));
+                }
+            };
+            indices.push(if hardened { index | 0x80000000 } else { index });
+        }
+
+        Ok(DerivationPath(indices))
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "generic-serialization", derive(Serialize, Deserialize))]
 enum DerivationType {
@@ -1163,6 +1253,91 @@ mod tests {
         assert_eq!(child_prv.public(), child_xpub);
     }
 
+    #[test]
+    fn derivation_path_bip44() {
+        let path: DerivationPath = "m/44'/1815'/0'/0/3".parse().unwrap();
+        assert_eq!(
+            path.as_ref(),
+            &[0x8000002C, 0x80000717, 0x80000000, 0, 3][..]
+        );
+        assert_eq!(format!("{}", path), "m/44'/1815'/0'/0/3");
+
+        let prv = XPrv::from_bytes_verified(D1).unwrap();
+        let ds = DerivationScheme::V2;
+        assert_eq!(
+            prv.derive_path(ds, &path),
+            prv.derive(ds, 0x8000002C)
+                .derive(ds, 0x80000717)
+                .derive(ds, 0x80000000)
+                .derive(ds, 0)
+                .derive(ds, 3)
+        );
+
+        let root: DerivationPath = "m".parse().unwrap();
+        assert!(root.is_empty());
+        assert_eq!(prv.derive_path(ds, &root), prv);
+    }
+
+    #[test]
+    fn derivation_path_malformed() {
+        let malformed = [
+            "",
+            "44'/1815'",
+            "M/44'",
+            "m/",
+            "m//0",
+            "m/a",
+            "m/-1",
+            "m/+1",
+            "m/1''",
+            "m/0x10",
+            "m/2147483648",
+            "m/4294967296'",
+        ];
+        for path in malformed.iter() {
+            match path.parse::<DerivationPath>() {
+                Err(Error::InvalidDerivationPath(_)) => {}
+                r => panic!("expected {:?} to be rejected, got {:?}", path, r),
+            }
+        }
+    }
+
+    #[test]
+    fn xprv_xpub_bytes_roundtrip() {
+        let prv = XPrv::from_bytes_verified(D1).unwrap();
+        assert_eq!(prv.as_ref(), &D1[..]);
+
+        let mut bytes = [0; XPRV_SIZE];
+        bytes.copy_from_slice(prv.as_ref());
+        assert_eq!(XPrv::from_bytes_verified(bytes).unwrap(), prv);
+
+        let xpub = prv.public();
+        assert_eq!(XPub::from_slice(xpub.as_ref()).unwrap(), xpub);
+        assert_eq!(
+            XPub::from_slice(&xpub.as_ref()[..32]),
+            Err(Error::InvalidXPubSize(32))
+        );
+    }
+
+    #[test]
+    fn xprv_from_zero_bytes() {
+        assert!(XPrv::from_bytes_verified([0; XPRV_SIZE]).is_err());
+    }
+
+    #[test]
+    fn xpub_derive_hardened() {
+        let prv = XPrv::from_bytes_verified(D1).unwrap();
+        let xpub = prv.public();
+        assert_eq!(
+            xpub.derive(DerivationScheme::V1, 0x80000000),
+            Err(Error::ExpectedSoftDerivation)
+        );
+        assert_eq!(
+            xpub.derive(DerivationScheme::V2, 0xffffffff),
+            Err(Error::ExpectedSoftDerivation)
+        );
+    }
+
     #[test]
     fn xprv_sign() {
         let prv = XPrv::from_bytes_verified(D1_H0).unwrap();
@@ -589,6 +589,48 @@ mod unit_tests {
         test_fee(HeadFirst::from(inputs), selected, outputs);
     }
 
+    #[test]
+    fn random_small_amount_ada_largest_first() {
+        let input1 = mk_icarus_style_input(Coin::new(2_000_000).unwrap());
+        let input2 = mk_daedalus_style_input(Coin::new(3_000_000).unwrap());
+        let input3 = mk_icarus_style_input(Coin::new(1_000_000).unwrap());
+        let output1 = mk_icarus_style_txout(Coin::new(1_000_000).unwrap());
+
+        let inputs = vec![input1, input2.clone(), input3];
+        let outputs = vec![output1];
+
+        let selected = vec![input2];
+
+        test_fee(LargestFirst::from(inputs), selected, outputs);
+    }
+
+    #[test]
+    fn random_large_amount_ada_largest_first() {
+        let input1 = mk_icarus_style_input(Coin::new(1_000_000).unwrap());
+        let input2 = mk_daedalus_style_input(Coin::new(3_000_000).unwrap());
+        let input3 = mk_icarus_style_input(Coin::new(2_000_000).unwrap());
+        let output1 = mk_icarus_style_txout(Coin::new(4_000_000).unwrap());
+
+        let inputs = vec![input1, input2.clone(), input3.clone()];
+        let outputs = vec![output1];
+
+        let selected = vec![input2, input3];
+
+        test_fee(LargestFirst::from(inputs), selected, outputs);
+    }
+
+    #[test]
+    fn not_enough_ada_largest_first() {
+        let input1 = mk_icarus_style_input(Coin::new(1_000_000).unwrap());
+        let input2 = mk_icarus_style_input(Coin::new(2_000_000).unwrap());
+        let output1 = mk_icarus_style_txout(Coin::new(3_000_000).unwrap());
+
+        let inputs = vec![input1, input2];
+        let outputs = vec![output1];
+
+        test_no_enough(LargestFirst::from(inputs), outputs);
+    }
+
     #[test]
     fn random_large_amount_ada_blackjack() {
         let input1 = mk_icarus_style_input(Coin::new(25_029_238_000000).unwrap());
@@ -35,22 +35,17 @@ extern crate test;
 #[macro_use]
 extern crate quickcheck;
 
-#[cfg(test)]
-extern crate rand;
-
 extern crate cryptoxide;
+extern crate rand;
 #[macro_use]
 extern crate cbor_event;
 
-extern crate chain_core;
-
-#[cfg(test)]
 extern crate base64;
+extern crate chain_core;
 
 pub mod address;
 pub mod coin;
 pub mod config;
-mod crc32;
 pub mod fee;
 pub mod hash;
 pub mod hdpayload;
@@ -1,3 +1,10 @@
+//! Merkle tree as used for the transactions proof of a block
+//!
+//! Leaves are hashed as `Blake2b256(0x00 | cbor(leaf))` and branches as
+//! `Blake2b256(0x01 | left | right)`. Unlike other merkle trees, odd
+//! nodes are not duplicated: a list of `n` leaves is split in a left
+//! subtree holding the largest power of two strictly lower than `n`
+//! and a right subtree holding the remaining leaves.
 use cbor_event::se;
 use hash::Blake2b256;
 
@@ -13,6 +20,45 @@ pub enum MerkleNode {
     Leaf(Hash),
 }
 
+/// One step of a [`MerkleProof`](./struct.MerkleProof.html), from the
+/// leaf up to the root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofStep {
+    /// the sibling node is on the left of the current node
+    Left(Hash),
+    /// the sibling node is on the right of the current node
+    Right(Hash),
+}
+
+/// Proof that a given leaf is part of a merkle tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub index: usize,
+    pub steps: Vec<ProofStep>,
+}
+
+fn hash_leaf<T>(x: &T) -> Hash
+where
+    T: se::Serialize,
+{
+    let bs = vec![0u8];
+    let mut se = se::Serializer::new(bs);
+    x.serialize(&mut se).unwrap();
+    Hash::new(&se.finalize())
+}
+
+fn hash_branch(a: &Hash, b: &Hash) -> Hash {
+    let mut bs = vec![1u8];
+    bs.extend(a.as_hash_bytes());
+    bs.extend(b.as_hash_bytes());
+    Hash::new(&bs)
+}
+
+/// number of leaves in the left subtree of a node holding `n` leaves
+fn split_point(n: usize) -> usize {
+    n.checked_next_power_of_two().unwrap() >> 1
+}
+
 impl MerkleTree {
     pub fn new<T>(xs: &[T]) -> Self
     where
@@ -31,6 +77,21 @@ impl MerkleTree {
             MerkleTree::Tree(_, node) => node.get_root_hash().clone(),
         }
     }
+
+    /// create the proof that the leaf at the given index is part of
+    /// the tree. Returns `None` if the index is out of bound.
+    pub fn proof_for(&self, index: usize) -> Option<MerkleProof> {
+        match self {
+            MerkleTree::Empty => None,
+            MerkleTree::Tree(n, _) if index >= *n => None,
+            MerkleTree::Tree(n, node) => {
+                let mut steps = Vec::new();
+                node.collect_proof(*n, index, &mut steps);
+                steps.reverse();
+                Some(MerkleProof { index, steps })
+            }
+        }
+    }
 }
 
 impl MerkleNode {
@@ -41,18 +102,13 @@ impl MerkleNode {
         if xs.is_empty() {
             panic!("make_tree applied to empty list")
         } else if xs.len() == 1 {
-            let bs = vec![0u8];
-            let mut se = se::Serializer::new(bs);
-            xs[0].serialize(&mut se).unwrap();
-            MerkleNode::Leaf(Hash::new(&se.finalize()))
+            MerkleNode::Leaf(hash_leaf(&xs[0]))
         } else {
-            let i = xs.len().checked_next_power_of_two().unwrap() >> 1;
+            let i = split_point(xs.len());
             let a = MerkleNode::make_tree(&xs[0..i]);
             let b = MerkleNode::make_tree(&xs[i..]);
-            let mut bs = vec![1u8];
-            bs.extend(a.get_root_hash().as_hash_bytes());
-            bs.extend(b.get_root_hash().as_hash_bytes());
-            MerkleNode::Branch(Hash::new(&bs), Box::new(a), Box::new(b))
+            let h = hash_branch(a.get_root_hash(), b.get_root_hash());
+            MerkleNode::Branch(h, Box::new(a), Box::new(b))
         }
     }
 
@@ -62,4 +118,108 @@ impl MerkleNode {
             MerkleNode::Leaf(hash) => hash,
         }
     }
+
+    // collect the siblings from the root down to the leaf `index`,
+    // `n` being the number of leaves under this node.
+    fn collect_proof(&self, n: usize, index: usize, steps: &mut Vec<ProofStep>) {
+        match self {
+            MerkleNode::Leaf(_) => {}
+            MerkleNode::Branch(_, a, b) => {
+                let i = split_point(n);
+                if index < i {
+                    steps.push(ProofStep::Right(b.get_root_hash().clone()));
+                    a.collect_proof(i, index, steps)
+                } else {
+                    steps.push(ProofStep::Left(a.get_root_hash().clone()));
+                    b.collect_proof(n - i, index - i, steps)
+                }
+            }
+        }
+    }
+}
+
+impl MerkleProof {
+    /// check the given leaf is part of the tree with the given root
+    pub fn verify<T>(&self, root: &Hash, leaf: &T) -> bool
+    where
+        T: se::Serialize,
+    {
+        let computed = self
+            .steps
+            .iter()
+            .fold(hash_leaf(leaf), |h, step| match step {
+                ProofStep::Left(sibling) => hash_branch(sibling, &h),
+                ProofStep::Right(sibling) => hash_branch(&h, sibling),
+            });
+        &computed == root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn leaves(n: u8) -> Vec<Hash> {
+        (0..n).map(|i| Hash::new(&[i])).collect()
+    }
+
+    fn check_tree(n: u8, expected_root: &str) {
+        let leaves = leaves(n);
+        let tree = MerkleTree::new(&leaves);
+        let root = tree.get_root_hash();
+        assert_eq!(root, Hash::from_str(expected_root).unwrap());
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof_for(index).unwrap();
+            assert!(proof.verify(&root, leaf));
+            // the proof does not hold for any other leaf
+            let other = &leaves[(index + 1) % leaves.len()];
+            assert_eq!(proof.verify(&root, other), n == 1);
+        }
+        assert!(tree.proof_for(leaves.len()).is_none());
+    }
+
+    #[test]
+    fn merkle_tree_1_leaf() {
+        check_tree(
+            1,
+            "7b0b6137976a7155cb31467b66b937507f0d5170bf8439a8b93a4084f804f415",
+        );
+    }
+
+    #[test]
+    fn merkle_tree_2_leaves() {
+        check_tree(
+            2,
+            "88a37bef035907b6b13d4334b43d52097b8d0d12189746f43576332fdfe0cda1",
+        );
+    }
+
+    #[test]
+    fn merkle_tree_3_leaves() {
+        check_tree(
+            3,
+            "18d77bbdef5e4dc2eebbaecead20e69a9d785ba87ab4023d53a038b65c3e0dae",
+        );
+        // the odd leaf is not duplicated, it is hashed next to the
+        // root of the first two leaves
+        let leaves = leaves(3);
+        let proof = MerkleTree::new(&leaves).proof_for(2).unwrap();
+        assert_eq!(proof.steps.len(), 1);
+    }
+
+    #[test]
+    fn merkle_tree_8_leaves() {
+        check_tree(
+            8,
+            "a250658e7529e5642b278d75311a00740cc9eff65f113dfb8c4b650cfcc7415d",
+        );
+    }
+
+    #[test]
+    fn merkle_tree_empty() {
+        let tree = MerkleTree::new::<Hash>(&[]);
+        assert!(tree.proof_for(0).is_none());
+    }
 }
@@ -7,14 +7,17 @@
 //! On the **mainnet** you can use the redeem keys to claim redeem addresses.
 //!
 
+use base64;
 use cbor_event::{self, de::Deserializer, se::Serializer};
 use cryptoxide::ed25519;
+use cryptoxide::util::fixed_time_eq;
 #[cfg(feature = "generic-serialization")]
 use serde;
-use util::hex;
+use util::{hex, secret::Secret};
 
 use std::{
     cmp, fmt,
+    hash::{Hash, Hasher},
     io::{BufRead, Write},
     result,
 };
@@ -26,6 +29,7 @@ pub enum Error {
     InvalidPrivateKeySize(usize),
     InvalidSignatureSize(usize),
     HexadecimalError(hex::Error),
+    Base64Error,
 }
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -46,6 +50,7 @@ impl fmt::Display for Error {
                 SIGNATURE_SIZE, sz
             ),
             &Error::HexadecimalError(_) => write!(f, "Invalid hexadecimal"),
+            &Error::Base64Error => write!(f, "Invalid base64"),
         }
     }
 }
@@ -65,9 +70,17 @@ impl ::std::error::Error for Error {
 
 pub type Result<T> = result::Result<T, Error>;
 
+/// decode base64, accepting both the URL safe alphabet (as used in the
+/// genesis data) and the standard one.
+fn decode_base64(s: &str) -> Result<Vec<u8>> {
+    base64::decode_config(s, base64::URL_SAFE)
+        .or_else(|_| base64::decode_config(s, base64::STANDARD))
+        .map_err(|_| Error::Base64Error)
+}
+
 pub const PUBLICKEY_SIZE: usize = 32;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
+#[derive(PartialOrd, Ord, Copy, Clone)]
 pub struct PublicKey([u8; PUBLICKEY_SIZE]);
 impl PublicKey {
     pub fn from_bytes(bytes: [u8; PUBLICKEY_SIZE]) -> Self {
@@ -88,6 +101,12 @@ impl PublicKey {
         Self::from_slice(&bytes)
     }
 
+    /// parse a base64 encoded key, as found in the genesis data
+    pub fn from_base64(s: &str) -> Result<Self> {
+        let bytes = decode_base64(s)?;
+        Self::from_slice(&bytes)
+    }
+
     pub fn verify(&self, signature: &Signature, bytes: &[u8]) -> bool {
         ed25519::verify(bytes, &self.0, signature.as_ref())
     }
@@ -97,6 +116,19 @@ impl AsRef<[u8]> for PublicKey {
         &self.0
     }
 }
+/// constant time comparison: the time taken does not depend on the
+/// content of the keys
+impl PartialEq for PublicKey {
+    fn eq(&self, other: &Self) -> bool {
+        fixed_time_eq(&self.0, &other.0)
+    }
+}
+impl Eq for PublicKey {}
+impl Hash for PublicKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
 impl fmt::Debug for PublicKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", hex::encode(self.as_ref()))
@@ -110,11 +142,13 @@ impl fmt::Display for PublicKey {
 
 pub const PRIVATEKEY_SIZE: usize = 32;
 
-#[derive(Clone)]
-pub struct PrivateKey([u8; PRIVATEKEY_SIZE]);
+/// constant time comparison: the time taken does not depend on the
+/// content of the keys
+#[derive(Clone, PartialEq, Eq)]
+pub struct PrivateKey(Secret<[u8; PRIVATEKEY_SIZE]>);
 impl fmt::Debug for PrivateKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", hex::encode(self.as_ref()))
+        write!(f, "PrivateKey({:?})", self.0)
     }
 }
 impl fmt::Display for PrivateKey {
@@ -124,7 +158,7 @@ impl fmt::Display for PrivateKey {
 }
 impl AsRef<[u8]> for PrivateKey {
     fn as_ref(&self) -> &[u8] {
-        &self.0
+        self.0.expose()
     }
 }
 impl PrivateKey {
@@ -140,7 +174,7 @@ impl PrivateKey {
     }
 
     fn from_bytes(bytes: [u8; PRIVATEKEY_SIZE]) -> Self {
-        PrivateKey(bytes)
+        PrivateKey(Secret::new(bytes))
     }
 
     pub fn from_slice(bytes: &[u8]) -> Result<Self> {
@@ -157,17 +191,23 @@ impl PrivateKey {
         Self::from_slice(&bytes)
     }
 
+    /// parse a base64 encoded key, as found in the genesis data
+    pub fn from_base64(s: &str) -> Result<Self> {
+        let bytes = decode_base64(s)?;
+        Self::from_slice(&bytes)
+    }
+
     pub fn generate(seed: &[u8]) -> Result<Self> {
         Self::from_slice(seed)
     }
 
     pub fn public(&self) -> PublicKey {
-        let (_, pk) = ed25519::keypair(&self.0);
+        let (_, pk) = ed25519::keypair(self.0.expose());
         PublicKey::from_bytes(pk)
     }
 
     pub fn sign(&self, bytes: &[u8]) -> Signature {
-        let (sk, _) = ed25519::keypair(&self.0);
+        let (sk, _) = ed25519::keypair(self.0.expose());
         Signature::from_bytes(ed25519::signature(bytes, &sk))
     }
 }
@@ -216,9 +256,11 @@ impl AsRef<[u8]> for Signature {
         &self.0
     }
 }
+/// constant time comparison: the time taken does not depend on the
+/// content of the signatures
 impl PartialEq for Signature {
     fn eq(&self, other: &Self) -> bool {
-        PartialEq::eq(&self.0[..], &other.0[..])
+        fixed_time_eq(&self.0, &other.0)
     }
 }
 impl Eq for Signature {}
@@ -265,7 +307,7 @@ impl cbor_event::se::Serialize for PrivateKey {
         &self,
         serializer: &'se mut Serializer<W>,
     ) -> cbor_event::Result<&'se mut Serializer<W>> {
-        serializer.write_bytes(self.0.as_ref())
+        serializer.write_bytes(self.as_ref())
     }
 }
 impl cbor_event::de::Deserialize for PrivateKey {
@@ -540,4 +582,49 @@ mod tests {
             public_key.verify(&signature, &data)
         }
     }
+
+    #[test]
+    fn constant_time_eq() {
+        let private_key = PrivateKey::from_bytes([0x42; PRIVATEKEY_SIZE]);
+        let other_key = PrivateKey::from_bytes([0x43; PRIVATEKEY_SIZE]);
+        assert_eq!(private_key, private_key.clone());
+        assert_ne!(private_key, other_key);
+        assert_eq!(private_key.public(), private_key.public());
+        assert_ne!(private_key.public(), other_key.public());
+
+        let signature = private_key.sign(b"some data");
+        assert_eq!(signature, private_key.sign(b"some data"));
+        assert_ne!(signature, private_key.sign(b"some other data"));
+
+        // only the last byte differs
+        let mut bytes = [0; SIGNATURE_SIZE];
+        bytes.copy_from_slice(signature.as_ref());
+        bytes[SIGNATURE_SIZE - 1] ^= 1;
+        assert_ne!(signature, Signature::from_bytes(bytes));
+    }
+
+    #[test]
+    fn public_key_from_base64() {
+        let expected = "fb40490e2fa06aeca59382e9b504e08cc7a8ee463d95309b66fd76bf03924d99";
+        let pk = PublicKey::from_base64("-0BJDi-gauylk4LptQTgjMeo7kY9lTCbZv12vwOSTZk=").unwrap();
+        assert_eq!(pk, PublicKey::from_hex(expected).unwrap());
+        let pk = PublicKey::from_base64("+0BJDi+gauylk4LptQTgjMeo7kY9lTCbZv12vwOSTZk=").unwrap();
+        assert_eq!(pk, PublicKey::from_hex(expected).unwrap());
+    }
+
+    #[test]
+    fn public_key_from_base64_invalid() {
+        assert_eq!(
+            PublicKey::from_base64("+0BJDi+gauylk4LptQTgjMeo7kY9lTCbZv12vwOS"),
+            Err(Error::InvalidPublicKeySize(30))
+        );
+        assert_eq!(
+            PrivateKey::from_base64("AAAA").unwrap_err(),
+            Error::InvalidPrivateKeySize(3)
+        );
+        assert_eq!(
+            PublicKey::from_base64("not base64!"),
+            Err(Error::Base64Error)
+        );
+    }
 }
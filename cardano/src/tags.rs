@@ -1,3 +1,13 @@
+//! Signing tags
+//!
+//! Every signed object is prefixed with a one byte tag telling what is
+//! signed, then the CBOR encoded protocol magic, so a signature is only
+//! valid for one kind of object on one network.
+//!
+use cbor_event::se::Serializer;
+use config::ProtocolMagic;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SigningTag {
     Tx = 0x01,
     RedeemTx = 0x02,
@@ -10,3 +20,36 @@ pub enum SigningTag {
     MainBlockHeavy = 0x09,
     ProxySK = 0x0a,
 }
+
+/// the bytes to sign for the given (already CBOR encoded) `body`:
+/// the tag, the protocol magic and the body
+pub fn signing_payload(tag: SigningTag, protocol_magic: ProtocolMagic, body: &[u8]) -> Vec<u8> {
+    let mut buf = vec![tag as u8];
+    Serializer::new(&mut buf)
+        .serialize(&protocol_magic)
+        .expect("serialize protocol magic");
+    buf.extend_from_slice(body);
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signing_payload_layout() {
+        let payload = signing_payload(SigningTag::Tx, ProtocolMagic::from(42), &[0xaa, 0xbb]);
+        assert_eq!(payload, vec![0x01, 0x18, 0x2a, 0xaa, 0xbb]);
+    }
+
+    #[test]
+    fn signing_payload_depends_on_network() {
+        let body = [0x82, 0x00, 0x01];
+        let mainnet = signing_payload(SigningTag::Tx, ProtocolMagic::default(), &body);
+        let testnet = signing_payload(SigningTag::Tx, ProtocolMagic::from(1097911063), &body);
+        assert_ne!(mainnet, testnet);
+
+        let redeem = signing_payload(SigningTag::RedeemTx, ProtocolMagic::default(), &body);
+        assert_ne!(mainnet, redeem);
+    }
+}
@@ -7,26 +7,95 @@
 //! `TxAux` : Signed Tx (Tx + Witness)
 //!
 use std::{
+    collections::{btree_map, BTreeMap, BTreeSet},
     fmt,
-    io::{BufRead, Write},
+    io::{BufRead, Cursor, Read, Write},
+    str::FromStr,
 };
 
 use crate::{
-    address::{AddrType, Attributes, ExtendedAddr, SpendingData},
+    address::{ExtendedAddr, SpendingData},
+    block::Block,
+    cbor,
     coin::{self, Coin},
     config::ProtocolMagic,
-    hash::Blake2b256,
+    hash::{self, Blake2b256},
+    hdpayload::HDKey,
     hdwallet::{Signature, XPrv, XPub, SIGNATURE_SIZE, XPUB_SIZE},
     merkle, redeem,
-    tags::SigningTag,
+    tags::{self, SigningTag},
+    util::try_from_slice::TryFromSlice,
 };
 
 use cbor_event::{self, de::Deserializer, se::Serializer};
 use chain_core::property;
 
-// Transaction IDs are either a hash of the CBOR serialisation of a
-// given Tx, or a hash of a redeem address.
-pub type TxId = Blake2b256;
+/// Transaction IDs are either a hash of the CBOR serialisation of a
+/// given Tx, or a hash of a redeem address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "generic-serialization", derive(Serialize, Deserialize))]
+pub struct TxId(Blake2b256);
+impl TxId {
+    pub const HASH_SIZE: usize = Blake2b256::HASH_SIZE;
+
+    pub fn new(bytes: &[u8]) -> Self {
+        TxId(Blake2b256::new(bytes))
+    }
+
+    pub fn as_hash_bytes(&self) -> &[u8; Blake2b256::HASH_SIZE] {
+        self.0.as_hash_bytes()
+    }
+}
+impl fmt::Display for TxId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+impl TryFromSlice for TxId {
+    type Error = <Blake2b256 as TryFromSlice>::Error;
+    fn try_from_slice(slice: &[u8]) -> ::std::result::Result<Self, Self::Error> {
+        Ok(Self::from(Blake2b256::try_from_slice(slice)?))
+    }
+}
+impl AsRef<[u8]> for TxId {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+impl From<TxId> for [u8; Blake2b256::HASH_SIZE] {
+    fn from(hash: TxId) -> Self {
+        hash.0.into()
+    }
+}
+impl From<[u8; Blake2b256::HASH_SIZE]> for TxId {
+    fn from(hash: [u8; Blake2b256::HASH_SIZE]) -> Self {
+        TxId(Blake2b256::from(hash))
+    }
+}
+impl From<Blake2b256> for TxId {
+    fn from(hash: Blake2b256) -> Self {
+        TxId(hash)
+    }
+}
+impl FromStr for TxId {
+    type Err = hash::Error;
+    fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+        Ok(Self::from(Blake2b256::from_str(s)?))
+    }
+}
+impl cbor_event::se::Serialize for TxId {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        serializer.serialize(&self.0)
+    }
+}
+impl cbor_event::de::Deserialize for TxId {
+    fn deserialize<R: BufRead>(raw: &mut Deserializer<R>) -> cbor_event::Result<Self> {
+        cbor_event::de::Deserialize::deserialize(raw).map(TxId)
+    }
+}
 
 impl property::TransactionId for TxId {}
 
@@ -34,15 +103,21 @@ pub fn redeem_pubkey_to_txid(
     pubkey: &redeem::PublicKey,
     protocol_magic: ProtocolMagic,
 ) -> (TxId, ExtendedAddr) {
-    let address = ExtendedAddr::new(
-        AddrType::ATRedeem,
-        SpendingData::RedeemASD(*pubkey),
-        Attributes::new_bootstrap_era(None, protocol_magic.into()),
-    );
-    let txid = Blake2b256::new(&cbor!(&address).unwrap());
+    let address = ExtendedAddr::from_redeem_pubkey(pubkey, protocol_magic);
+    let txid = TxId::new(&cbor!(&address).unwrap());
     (txid, address)
 }
 
+/// sign the given transaction with the redeem key, to spend AVVM funds
+/// from the genesis UTxO (see [`redeem_pubkey_to_txid`](./fn.redeem_pubkey_to_txid.html)).
+pub fn sign_redeem_tx(
+    protocol_magic: ProtocolMagic,
+    tx: &Tx,
+    key: &redeem::PrivateKey,
+) -> TxInWitness {
+    TxInWitness::new_redeem_pk(protocol_magic, key, &tx.id())
+}
+
 /// Tx Output composed of an address and a coin value
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "generic-serialization", derive(Serialize, Deserialize))]
@@ -145,14 +220,8 @@ impl TxInWitness {
         sign_tag: SigningTag,
         txid: &TxId,
     ) -> Vec<u8> {
-        let mut se = Serializer::new_vec();
-        se.write_unsigned_integer(sign_tag as u64)
-            .expect("write the sign tag")
-            .serialize(&protocol_magic)
-            .expect("serialize protocol magic")
-            .serialize(txid)
-            .expect("serialize Tx's Id");
-        se.finalize()
+        let txid = cbor!(txid).expect("serialize Tx's Id");
+        tags::signing_payload(sign_tag, protocol_magic, &txid)
     }
 
     /// verify a given extended address is associated to the witness.
@@ -199,6 +268,16 @@ impl TxInWitness {
         self.verify_address(address) && self.verify_tx(protocol_magic, tx)
     }
 }
+
+/// sign the given transaction with the given keys, `keys[i]` being the
+/// private key of `tx.inputs[i]`. There is one witness per key, in the
+/// same order.
+pub fn sign_tx(protocol_magic: ProtocolMagic, tx: &Tx, keys: &[XPrv]) -> Vec<TxInWitness> {
+    let txid = tx.id();
+    keys.iter()
+        .map(|key| TxInWitness::new_extended_pk(protocol_magic, key, &txid))
+        .collect()
+}
 impl cbor_event::se::Serialize for TxInWitness {
     fn serialize<'se, W: Write>(
         &self,
@@ -337,15 +416,173 @@ impl cbor_event::de::Deserialize for TxoPointer {
     }
 }
 
+/// Attributes of a transaction.
+///
+/// So far, there is no known transaction attribute. The attributes are
+/// kept unparsed (the key and the CBOR encoded value, exactly as read)
+/// so a transaction carrying some re-encodes to the same bytes, even
+/// if they are not canonical, and keeps the same `TxId`.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "generic-serialization", derive(Serialize, Deserialize))]
+pub struct TxAttributes(BTreeMap<u64, Vec<u8>>);
+impl TxAttributes {
+    pub fn new() -> Self {
+        TxAttributes(BTreeMap::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// get the CBOR encoded value of the given attribute
+    pub fn get(&self, key: u64) -> Option<&[u8]> {
+        self.0.get(&key).map(|v| v.as_slice())
+    }
+
+    /// set the value of the given attribute, fails if `value` is not
+    /// exactly one CBOR encoded item.
+    pub fn insert(&mut self, key: u64, value: Vec<u8>) -> cbor_event::Result<Option<Vec<u8>>> {
+        let mut cursor = Cursor::new(value.as_slice());
+        let mut item = Vec::with_capacity(value.len());
+        read_raw_item(&mut cursor, &mut item)?;
+        if item.len() != value.len() {
+            return Err(cbor_event::Error::CustomError(String::from(
+                "Invalid TxAttributes value: trailing data",
+            )));
+        }
+        Ok(self.0.insert(key, value))
+    }
+}
+
+fn unexpected_end() -> cbor_event::Error {
+    cbor_event::Error::CustomError(String::from("Invalid CBOR item: unexpected end of input"))
+}
+
+// append `len` bytes from `reader` to `out`
+fn read_raw_bytes<R: BufRead>(
+    reader: &mut R,
+    len: u64,
+    out: &mut Vec<u8>,
+) -> cbor_event::Result<()> {
+    let read = reader.by_ref().take(len).read_to_end(out)?;
+    if read as u64 != len {
+        return Err(unexpected_end());
+    }
+    Ok(())
+}
+
+// consume the break stop code ending an indefinite length item, if it
+// is the next byte
+fn read_raw_break<R: BufRead>(reader: &mut R, out: &mut Vec<u8>) -> cbor_event::Result<bool> {
+    let is_break = match reader.fill_buf()?.first() {
+        None => return Err(unexpected_end()),
+        Some(byte) => *byte == 0xff,
+    };
+    if is_break {
+        reader.consume(1);
+        out.push(0xff);
+    }
+    Ok(is_break)
+}
+
+// append the head of the next CBOR item of `reader` to `out` and return
+// its major type and argument (`None` for an indefinite length)
+fn read_raw_head<R: BufRead>(
+    reader: &mut R,
+    out: &mut Vec<u8>,
+) -> cbor_event::Result<(u8, Option<u64>)> {
+    read_raw_bytes(reader, 1, out)?;
+    let initial = out[out.len() - 1];
+    let (major, info) = (initial >> 5, initial & 0x1f);
+    let argument = match info {
+        0..=23 => Some(u64::from(info)),
+        24..=27 => {
+            let size = 1 << (info - 24);
+            read_raw_bytes(reader, size, out)?;
+            let bytes = &out[out.len() - size as usize..];
+            Some(bytes.iter().fold(0, |acc, b| acc << 8 | u64::from(*b)))
+        }
+        31 if (2..=5).contains(&major) => None,
+        _ => {
+            return Err(cbor_event::Error::CustomError(format!(
+                "Invalid CBOR item: unexpected initial byte 0x{:02x}",
+                initial
+            )));
+        }
+    };
+    Ok((major, argument))
+}
+
+/// maximum nesting of arrays, maps and tags accepted in a transaction
+/// attribute value, so a peer cannot exhaust the stack
+const MAX_RAW_ITEM_DEPTH: usize = 64;
+
+// append the bytes of the next CBOR item of `reader` to `out`, without
+// decoding it, so non canonical encodings are kept as they are.
+fn read_raw_item<R: BufRead>(reader: &mut R, out: &mut Vec<u8>) -> cbor_event::Result<()> {
+    read_raw_item_at(reader, out, 0)
+}
+
+fn read_raw_item_at<R: BufRead>(
+    reader: &mut R,
+    out: &mut Vec<u8>,
+    depth: usize,
+) -> cbor_event::Result<()> {
+    if depth > MAX_RAW_ITEM_DEPTH {
+        return Err(cbor_event::Error::CustomError(format!(
+            "Invalid CBOR item: nested deeper than {} levels",
+            MAX_RAW_ITEM_DEPTH
+        )));
+    }
+    let (major, argument) = read_raw_head(reader, out)?;
+    match (major, argument) {
+        (2, Some(len)) | (3, Some(len)) => read_raw_bytes(reader, len, out),
+        (2, None) | (3, None) => {
+            // the chunks must be definite strings of the same major type
+            while !read_raw_break(reader, out)? {
+                match read_raw_head(reader, out)? {
+                    (chunk_major, Some(len)) if chunk_major == major => {
+                        read_raw_bytes(reader, len, out)?
+                    }
+                    _ => {
+                        return Err(cbor_event::Error::CustomError(String::from(
+                            "Invalid CBOR item: invalid chunk in indefinite length string",
+                        )));
+                    }
+                }
+            }
+            Ok(())
+        }
+        (4, Some(len)) => (0..len).try_for_each(|_| read_raw_item_at(reader, out, depth + 1)),
+        (5, Some(len)) => (0..len).try_for_each(|_| {
+            read_raw_item_at(reader, out, depth + 1)?;
+            read_raw_item_at(reader, out, depth + 1)
+        }),
+        (6, Some(_)) => read_raw_item_at(reader, out, depth + 1),
+        (4, None) => {
+            while !read_raw_break(reader, out)? {
+                read_raw_item_at(reader, out, depth + 1)?;
+            }
+            Ok(())
+        }
+        (5, None) => {
+            while !read_raw_break(reader, out)? {
+                read_raw_item_at(reader, out, depth + 1)?;
+                read_raw_item_at(reader, out, depth + 1)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
 /// A Transaction containing tx inputs and tx outputs.
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "generic-serialization", derive(Serialize, Deserialize))]
 pub struct Tx {
     pub inputs: Vec<TxoPointer>,
     pub outputs: Vec<TxOut>,
-    // attributes: TxAttributes
-    //
-    // So far, there is no TxAttributes... the structure contains only the unparsed/unknown stuff
+    attributes: TxAttributes,
 }
 impl fmt::Display for Tx {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -366,8 +603,15 @@ impl Tx {
         Tx {
             inputs: ins,
             outputs: outs,
+            attributes: TxAttributes::new(),
         }
     }
+    pub fn attributes(&self) -> &TxAttributes {
+        &self.attributes
+    }
+    pub fn attributes_mut(&mut self) -> &mut TxAttributes {
+        &mut self.attributes
+    }
     pub fn id(&self) -> TxId {
         let buf = cbor!(self).expect("encode Tx");
         TxId::new(&buf)
@@ -394,7 +638,7 @@ impl cbor_event::se::Serialize for Tx {
         serializer.write_array(cbor_event::Len::Len(3))?;
         cbor_event::se::serialize_indefinite_array(self.inputs.iter(), serializer)?;
         cbor_event::se::serialize_indefinite_array(self.outputs.iter(), serializer)?;
-        serializer.write_map(cbor_event::Len::Len(0))
+        serializer.serialize(&self.attributes)
     }
 }
 impl cbor_event::de::Deserialize for Tx {
@@ -404,15 +648,47 @@ impl cbor_event::de::Deserialize for Tx {
         // Note: these must be indefinite-size arrays.
         let inputs = cbor_event::de::Deserialize::deserialize(raw)?;
         let outputs = cbor_event::de::Deserialize::deserialize(raw)?;
+        let attributes = cbor_event::de::Deserialize::deserialize(raw)?;
 
-        let map_len = raw.map()?;
-        if !map_len.is_null() {
-            return Err(cbor_event::Error::CustomError(format!(
-                "Invalid Tx: we do not support Tx extra data... {:?} elements",
-                map_len
-            )));
+        Ok(Tx {
+            inputs,
+            outputs,
+            attributes,
+        })
+    }
+}
+impl cbor_event::se::Serialize for TxAttributes {
+    fn serialize<'se, W: Write>(
+        &self,
+        serializer: &'se mut Serializer<W>,
+    ) -> cbor_event::Result<&'se mut Serializer<W>> {
+        serializer.write_map(cbor_event::Len::Len(self.0.len() as u64))?;
+        for (key, value) in self.0.iter() {
+            serializer
+                .write_unsigned_integer(*key)?
+                .write_raw_bytes(value)?;
+        }
+        Ok(serializer)
+    }
+}
+impl cbor_event::de::Deserialize for TxAttributes {
+    fn deserialize<R: BufRead>(raw: &mut Deserializer<R>) -> cbor_event::Result<Self> {
+        let len = match raw.map()? {
+            cbor_event::Len::Len(len) => len,
+            cbor_event::Len::Indefinite => {
+                return Err(cbor_event::Error::CustomError(String::from(
+                    "Invalid TxAttributes: indefinite map not supported",
+                )));
+            }
+        };
+        let mut attributes = TxAttributes::new();
+        for _ in 0..len {
+            let key = raw.unsigned_integer()?;
+            let mut value = Vec::new();
+            read_raw_item(raw.as_mut_ref(), &mut value)?;
+            attributes.0.insert(key, value);
         }
-        Ok(Tx::new_with(inputs, outputs))
+        Ok(attributes)
     }
 }
 
@@ -521,6 +797,27 @@ impl fmt::Display for TxAux {
         writeln!(f, "witnesses: {:?}\n", self.witness)
     }
 }
+/// error when the number of witnesses does not match the number of
+/// inputs of the transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WitnessCountError {
+    MissingWitnesses,
+    UnexpectedWitnesses,
+}
+impl fmt::Display for WitnessCountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WitnessCountError::MissingWitnesses => {
+                write!(f, "transaction has more inputs than witnesses")
+            }
+            WitnessCountError::UnexpectedWitnesses => {
+                write!(f, "transaction has more witnesses than inputs")
+            }
+        }
+    }
+}
+impl ::std::error::Error for WitnessCountError {}
+
 impl TxAux {
     pub fn new(tx: Tx, witness: TxWitness) -> Self {
         TxAux {
@@ -528,6 +825,21 @@ impl TxAux {
             witness: witness,
         }
     }
+
+    /// assemble a `TxAux`, checking there is exactly one witness per
+    /// input of the transaction.
+    ///
+    /// The witnesses themselves are not verified, use
+    /// [`Verify::verify`](../block/verify/trait.Verify.html) for that.
+    pub fn from_witnesses(tx: Tx, witnesses: Vec<TxInWitness>) -> Result<Self, WitnessCountError> {
+        if tx.inputs.len() < witnesses.len() {
+            return Err(WitnessCountError::UnexpectedWitnesses);
+        }
+        if tx.inputs.len() > witnesses.len() {
+            return Err(WitnessCountError::MissingWitnesses);
+        }
+        Ok(TxAux::new(tx, TxWitness::from(witnesses)))
+    }
 }
 impl cbor_event::de::Deserialize for TxAux {
     fn deserialize<R: BufRead>(raw: &mut Deserializer<R>) -> cbor_event::Result<Self> {
@@ -561,23 +873,18 @@ where
 }
 
 pub fn txaux_serialize_size(tx: &Tx, in_witnesses: &Vec<TxInWitness>) -> usize {
-    use std::io::Write;
-
-    struct Cborsize(usize);
-    impl Write for Cborsize {
-        fn write(&mut self, bytes: &[u8]) -> ::std::result::Result<usize, ::std::io::Error> {
-            self.0 += bytes.len();
-            Ok(bytes.len())
-        }
-        fn flush(&mut self) -> ::std::result::Result<(), ::std::io::Error> {
-            Ok(())
+    // serialize the components as a `TxAux` without cloning them
+    struct TxAuxRef<'a>(&'a Tx, &'a Vec<TxInWitness>);
+    impl<'a> cbor_event::se::Serialize for TxAuxRef<'a> {
+        fn serialize<'se, W: Write>(
+            &self,
+            serializer: &'se mut Serializer<W>,
+        ) -> cbor_event::Result<&'se mut Serializer<W>> {
+            txaux_serialize(self.0, self.1, serializer)
         }
     }
 
-    let mut ser = cbor_event::se::Serializer::new(Cborsize(0));
-    txaux_serialize(tx, in_witnesses, &mut ser).unwrap();
-    let cborsize = ser.finalize();
-    cborsize.0
+    cbor::encoded_len(&TxAuxRef(tx, in_witnesses))
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -642,6 +949,107 @@ impl cbor_event::de::Deserialize for TxProof {
     }
 }
 
+/// Error when applying a transaction to a [`Utxos`](./struct.Utxos.html) set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UtxoError {
+    /// the transaction spends an output which is not in the set:
+    /// either it does not exist or it has already been spent
+    MissingInput(TxoPointer),
+}
+impl fmt::Display for UtxoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &UtxoError::MissingInput(ref ptr) => write!(f, "Missing unspent output {}", ptr),
+        }
+    }
+}
+impl ::std::error::Error for UtxoError {}
+
+/// Set of the unspent transaction outputs (UTxOs), indexed by the
+/// `TxoPointer` referencing them.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Utxos(BTreeMap<TxoPointer, TxOut>);
+impl Utxos {
+    pub fn new() -> Self {
+        Utxos(BTreeMap::new())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, ptr: &TxoPointer) -> Option<&TxOut> {
+        self.0.get(ptr)
+    }
+
+    pub fn iter(&self) -> btree_map::Iter<TxoPointer, TxOut> {
+        self.0.iter()
+    }
+
+    /// add an unspent output to the set (e.g. from the genesis data)
+    pub fn insert(&mut self, ptr: TxoPointer, txout: TxOut) -> Option<TxOut> {
+        self.0.insert(ptr, txout)
+    }
+
+    /// sum of the values of the outputs owned by the wallet of the
+    /// given `HDKey` (see `ExtendedAddr::is_owned_by`). This is the
+    /// balance available to the wallet.
+    pub fn balance_for(&self, hdkey: &HDKey) -> coin::Result<Coin> {
+        self.0
+            .values()
+            .filter(|txout| txout.address.is_owned_by(hdkey).is_some())
+            .try_fold(Coin::zero(), |acc, txout| acc.checked_add(txout.value))
+    }
+
+    /// remove the outputs spent by the transaction and add its new
+    /// outputs to the set.
+    ///
+    /// If one of the inputs is not in the set (or is spent twice by
+    /// the transaction) an error is returned and the set is left
+    /// untouched.
+    pub fn apply_tx(&mut self, tx: &Tx) -> Result<(), UtxoError> {
+        let mut spent = BTreeSet::new();
+        for input in tx.inputs.iter() {
+            if !self.0.contains_key(input) || !spent.insert(input) {
+                return Err(UtxoError::MissingInput(input.clone()));
+            }
+        }
+
+        for input in tx.inputs.iter() {
+            self.0.remove(input);
+        }
+        let id = tx.id();
+        for (index, output) in tx.outputs.iter().enumerate() {
+            self.0
+                .insert(TxoPointer::new(id, index as u32), output.clone());
+        }
+        Ok(())
+    }
+
+    /// apply all the transactions of the block, in order.
+    ///
+    /// Boundary blocks do not contain transactions and leave the set
+    /// unchanged. On error, the transactions preceding the faulty one
+    /// remain applied.
+    pub fn apply_block(&mut self, block: &Block) -> Result<(), UtxoError> {
+        if let &Block::MainBlock(ref blk) = block {
+            for txaux in blk.body.tx.iter() {
+                self.apply_tx(&txaux.tx)?;
+            }
+        }
+        Ok(())
+    }
+}
+impl From<BTreeMap<TxoPointer, TxOut>> for Utxos {
+    fn from(utxos: BTreeMap<TxoPointer, TxOut>) -> Self {
+        Utxos(utxos)
+    }
+}
+
 impl chain_core::property::Serialize for Tx {
     type Error = cbor_event::Error;
 
@@ -797,6 +1205,33 @@ mod tests {
         assert_eq!(txout.address.attributes.derivation_path, Some(hdap));
     }
 
+    #[test]
+    fn sign_tx_witnesses_verify() {
+        let protocol_magic = ProtocolMagic::default();
+        let keys: Vec<_> = (0..3)
+            .map(|i| hdwallet::XPrv::generate_from_seed(&hdwallet::Seed::from_bytes([i; 32])))
+            .collect();
+        let tx = Tx::new_with(
+            (0..3)
+                .map(|i| TxoPointer::new(TxId::new(&[i; 32]), u32::from(i)))
+                .collect(),
+            vec![txout(42)],
+        );
+
+        let witnesses = sign_tx(protocol_magic, &tx, &keys);
+        assert_eq!(witnesses.len(), tx.inputs.len());
+        for (witness, key) in witnesses.iter().zip(keys.iter()) {
+            assert_eq!(
+                witness,
+                &TxInWitness::new_extended_pk(protocol_magic, key, &tx.id())
+            );
+            let address = address::ExtendedAddr::new_simple(key.public(), NetworkMagic::NoMagic);
+            assert!(witness.verify_tx(protocol_magic, &tx));
+            assert!(witness.verify(protocol_magic, &address, &tx));
+        }
+        assert!(!witnesses[0].verify_tx(ProtocolMagic::from(1097911063), &tx));
+    }
+
     #[test]
     fn txout_encode_decode() {
         let seed = hdwallet::Seed::from_bytes(SEED);
@@ -843,6 +1278,176 @@ mod tests {
         assert_eq!(Some(txout), tx.outputs.pop());
     }
 
+    #[test]
+    fn tx_with_attributes_encode_decode() {
+        // the `TX` with the attributes `{ 0: h'010203', 7: [5] }`, the 5
+        // not being canonically encoded
+        let mut bytes = TX[..TX.len() - 1].to_vec();
+        bytes.extend_from_slice(&[0xa2, 0x00, 0x43, 0x01, 0x02, 0x03, 0x07, 0x81, 0x18, 0x05]);
+
+        let mut raw = Deserializer::from(std::io::Cursor::new(&bytes));
+        let tx: Tx = raw.deserialize().unwrap();
+        assert_eq!(tx.attributes.get(0), Some(&[0x43, 0x01, 0x02, 0x03][..]));
+        assert_eq!(tx.attributes.get(7), Some(&[0x81, 0x18, 0x05][..]));
+        assert_eq!(tx.attributes.get(1), None);
+
+        assert_eq!(cbor!(&tx).unwrap(), bytes);
+        assert_ne!(tx.id(), TxId::new(TX));
+        assert!(cbor_event::test_encode_decode(&tx).expect("encode/decode Tx"));
+    }
+
+    #[test]
+    fn tx_attributes_insert() {
+        let mut attributes = TxAttributes::new();
+        assert_eq!(
+            attributes.insert(0, vec![0x43, 0x01, 0x02, 0x03]).unwrap(),
+            None
+        );
+        // indefinite length array
+        assert!(attributes
+            .insert(1, vec![0x9f, 0x01, 0x18, 0x05, 0xff])
+            .is_ok());
+        assert_eq!(
+            attributes.insert(0, vec![0x00]).unwrap(),
+            Some(vec![0x43, 0x01, 0x02, 0x03])
+        );
+
+        // missing bytes, trailing data, missing break and no item at all
+        assert!(attributes.insert(2, vec![0x43, 0x01]).is_err());
+        assert!(attributes.insert(2, vec![0x00, 0x00]).is_err());
+        assert!(attributes.insert(2, vec![0x9f, 0x01]).is_err());
+        assert!(attributes.insert(2, vec![]).is_err());
+        assert_eq!(attributes.get(2), None);
+
+        let mut tx = Tx::new();
+        *tx.attributes_mut() = attributes;
+        let mut raw = Deserializer::from(std::io::Cursor::new(cbor!(&tx).unwrap()));
+        let decoded: Tx = raw.deserialize().unwrap();
+        assert_eq!(decoded, tx);
+    }
+
+    #[test]
+    fn tx_attributes_invalid_chunk() {
+        let mut attributes = TxAttributes::new();
+        // an indefinite byte string of definite byte string chunks
+        assert!(attributes
+            .insert(0, vec![0x5f, 0x41, 0x01, 0x40, 0xff])
+            .is_ok());
+        // a text string chunk in a byte string
+        assert!(attributes.insert(1, vec![0x5f, 0x61, 0x61, 0xff]).is_err());
+        // an indefinite chunk in a text string
+        assert!(attributes.insert(1, vec![0x7f, 0x7f, 0xff, 0xff]).is_err());
+        // an integer chunk
+        assert!(attributes.insert(1, vec![0x5f, 0x01, 0xff]).is_err());
+        assert_eq!(attributes.get(1), None);
+    }
+
+    #[test]
+    fn tx_attributes_deep_nesting() {
+        let mut attributes = TxAttributes::new();
+        let nested = |depth: usize| {
+            let mut value = vec![0x81; depth];
+            value.push(0x00);
+            value
+        };
+        assert!(attributes.insert(0, nested(MAX_RAW_ITEM_DEPTH)).is_ok());
+        assert!(attributes
+            .insert(1, nested(MAX_RAW_ITEM_DEPTH + 1))
+            .is_err());
+
+        // the same limit applies when decoding a transaction: `[ [], [], { 1: [[[...0]]] } ]`
+        let mut bytes = vec![0x83, 0x80, 0x80, 0xa1, 0x01];
+        bytes.extend(vec![0x9f; 100_000]);
+        let mut raw = Deserializer::from(std::io::Cursor::new(bytes));
+        assert!(raw.deserialize::<Tx>().is_err());
+    }
+
+    fn txout(value: u64) -> TxOut {
+        let seed = hdwallet::Seed::from_bytes(SEED);
+        let pk = hdwallet::XPrv::generate_from_seed(&seed).public();
+        let ea = address::ExtendedAddr::new_simple(pk, NetworkMagic::NoMagic);
+        TxOut::new(ea, Coin::new(value).unwrap())
+    }
+
+    #[test]
+    fn utxos_apply_tx_sequence() {
+        let genesis = TxoPointer::new(TxId::new(&[0; 32]), 0);
+        let mut utxos = Utxos::new();
+        utxos.insert(genesis.clone(), txout(100));
+
+        let tx1 = Tx::new_with(vec![genesis.clone()], vec![txout(60), txout(40)]);
+        utxos.apply_tx(&tx1).unwrap();
+        assert_eq!(utxos.len(), 2);
+        assert!(utxos.get(&genesis).is_none());
+        assert_eq!(utxos.get(&TxoPointer::new(tx1.id(), 1)), Some(&txout(40)));
+
+        // the second transaction spends the first output of the first one
+        let tx2 = Tx::new_with(vec![TxoPointer::new(tx1.id(), 0)], vec![txout(60)]);
+        utxos.apply_tx(&tx2).unwrap();
+        assert_eq!(utxos.len(), 2);
+        assert!(utxos.get(&TxoPointer::new(tx1.id(), 0)).is_none());
+        assert_eq!(utxos.get(&TxoPointer::new(tx2.id(), 0)), Some(&txout(60)));
+
+        // outputs can only be spent once
+        assert_eq!(
+            utxos.apply_tx(&tx2),
+            Err(UtxoError::MissingInput(TxoPointer::new(tx1.id(), 0)))
+        );
+    }
+
+    #[test]
+    fn utxos_apply_tx_missing_input() {
+        let genesis = TxoPointer::new(TxId::new(&[0; 32]), 0);
+        let missing = TxoPointer::new(TxId::new(&[1; 32]), 0);
+        let mut utxos = Utxos::new();
+        utxos.insert(genesis.clone(), txout(100));
+        let before = utxos.clone();
+
+        let tx = Tx::new_with(vec![genesis.clone(), missing.clone()], vec![txout(100)]);
+        assert_eq!(utxos.apply_tx(&tx), Err(UtxoError::MissingInput(missing)));
+        assert_eq!(utxos, before);
+
+        let tx = Tx::new_with(vec![genesis.clone(), genesis.clone()], vec![txout(200)]);
+        assert_eq!(utxos.apply_tx(&tx), Err(UtxoError::MissingInput(genesis)));
+        assert_eq!(utxos, before);
+    }
+
+    #[test]
+    fn utxos_balance_for() {
+        let seed = hdwallet::Seed::from_bytes(SEED);
+        let root = hdwallet::XPrv::generate_from_seed(&seed).public();
+        let hdkey = hdpayload::HDKey::new(&root);
+        let other_seed = hdwallet::Seed::from_bytes([1; hdwallet::SEED_SIZE]);
+        let other_root = hdwallet::XPrv::generate_from_seed(&other_seed).public();
+        let other_hdkey = hdpayload::HDKey::new(&other_root);
+
+        let hd_txout = |hdkey: &hdpayload::HDKey, index: u32, value: u64| {
+            let path = hdpayload::Path::new(vec![0x80000000, index]);
+            let attrs = address::Attributes::new_bootstrap_era(
+                Some(hdkey.encrypt_path(&path)),
+                NetworkMagic::NoMagic,
+            );
+            let ea = address::ExtendedAddr::new(
+                address::AddrType::ATPubKey,
+                address::SpendingData::PubKeyASD(root.clone()),
+                attrs,
+            );
+            TxOut::new(ea, Coin::new(value).unwrap())
+        };
+
+        let mut utxos = Utxos::new();
+        let id = TxId::new(&[0; 32]);
+        utxos.insert(TxoPointer::new(id, 0), hd_txout(&hdkey, 0, 10));
+        utxos.insert(TxoPointer::new(id, 1), hd_txout(&other_hdkey, 1, 20));
+        utxos.insert(TxoPointer::new(id, 2), hd_txout(&hdkey, 2, 30));
+        // no derivation path: cannot be recognised as ours
+        utxos.insert(TxoPointer::new(id, 3), txout(40));
+
+        assert_eq!(utxos.balance_for(&hdkey), Coin::new(40));
+        assert_eq!(utxos.balance_for(&other_hdkey), Coin::new(20));
+        assert_eq!(Utxos::new().balance_for(&hdkey), Ok(Coin::zero()));
+    }
+
     #[test]
     fn tx_encode_decode() {
         let txid = TxId::new(&[0; 32]);
@@ -953,6 +1558,49 @@ mod tests {
 
         assert!(cbor_event::test_encode_decode(&txaux).expect("encode/decode TxAux"));
     }
+
+    #[test]
+    fn sign_redeem_tx_verify() {
+        use block::Verify;
+
+        let pm = ProtocolMagic::default();
+        let key = redeem::PrivateKey::generate(&[7; redeem::PRIVATEKEY_SIZE]).unwrap();
+        let (txid, address) = redeem_pubkey_to_txid(&key.public(), pm);
+        let tx = Tx::new_with(vec![TxoPointer::new(txid, 0)], vec![txout(42)]);
+
+        let witness = sign_redeem_tx(pm, &tx, &key);
+        assert!(witness.verify(pm, &address, &tx));
+        assert!(!witness.verify_tx(ProtocolMagic::from(1097911063), &tx));
+
+        let txaux = TxAux::from_witnesses(tx, vec![witness]).unwrap();
+        assert!(txaux.verify(pm).is_ok());
+    }
+
+    #[test]
+    fn txaux_from_witnesses() {
+        use block::Verify;
+
+        let pm = ProtocolMagic::default();
+        let seed = hdwallet::Seed::from_bytes(SEED);
+        let sk = hdwallet::XPrv::generate_from_seed(&seed);
+        let tx = Tx::new_with(
+            vec![TxoPointer::new(TxId::new(&[0; 32]), 0)],
+            vec![txout(42)],
+        );
+        let witness = TxInWitness::new(pm, &sk, &tx.id());
+
+        let txaux = TxAux::from_witnesses(tx.clone(), vec![witness.clone()]).unwrap();
+        assert!(txaux.verify(pm).is_ok());
+
+        assert_eq!(
+            TxAux::from_witnesses(tx.clone(), vec![]),
+            Err(WitnessCountError::MissingWitnesses)
+        );
+        assert_eq!(
+            TxAux::from_witnesses(tx, vec![witness.clone(), witness]),
+            Err(WitnessCountError::UnexpectedWitnesses)
+        );
+    }
 }
 
 #[cfg(feature = "with-bench")]
@@ -8,11 +8,14 @@
 //! total flexibility and abstraction/helpers.
 //!
 
+use address::ExtendedAddr;
 use coin::{Coin, CoinDiff};
 use fee::{Fee, FeeAlgorithm};
 use std::iter::Iterator;
 use std::{error, fmt, iter, result};
-use tx::{txaux_serialize_size, Tx, TxAux, TxInWitness, TxOut, TxWitness, TxoPointer};
+use tx::{
+    txaux_serialize_size, Tx, TxAttributes, TxAux, TxInWitness, TxOut, TxWitness, TxoPointer,
+};
 use txutils::OutputPolicy;
 use {coin, fee};
 
@@ -21,12 +24,15 @@ use {coin, fee};
 pub struct TxBuilder {
     inputs: Vec<(TxoPointer, Coin)>,
     outputs: Vec<TxOut>,
+    attributes: TxAttributes,
 }
 
 #[derive(Debug)]
 pub enum Error {
     TxInvalidNoInput,
     TxInvalidNoOutput,
+    /// the output at the given index has a value of zero
+    TxInvalidZeroOutput(usize),
     TxNotEnoughTotalInput,
     TxOverLimit(usize),
     /// this return as by-product the amount of spare coins left behind
@@ -42,6 +48,9 @@ impl fmt::Display for Error {
         match self {
             Error::TxInvalidNoInput => write!(f, "Transaction is invalid, no input."),
             Error::TxInvalidNoOutput => write!(f, "Transaction is invalid, no output."),
+            Error::TxInvalidZeroOutput(idx) => {
+                write!(f, "Transaction is invalid, output {} has no value.", idx)
+            }
             Error::TxNotEnoughTotalInput => {
                 write!(f, "Transaction is invalid, already not enough input coins.")
             }
@@ -97,6 +106,7 @@ impl TxBuilder {
         TxBuilder {
             inputs: Vec::new(),
             outputs: Vec::new(),
+            attributes: TxAttributes::new(),
         }
     }
 
@@ -119,6 +129,12 @@ impl TxBuilder {
         self.outputs.push(o.clone())
     }
 
+    /// Set the attributes of the transaction, replacing any
+    /// previously set. They are taken into account in the fee.
+    pub fn set_attributes(&mut self, attributes: TxAttributes) {
+        self.attributes = attributes
+    }
+
     fn apply_policy_with(&mut self, output_policy: &OutputPolicy, leftover: Coin) -> Vec<TxOut> {
         match output_policy {
             OutputPolicy::One(change_addr) => {
@@ -212,6 +228,26 @@ impl TxBuilder {
         }
     }
 
+    /// Create the transaction, sending the leftover of the inputs
+    /// (minus the fee) to `change_addr`.
+    ///
+    /// All the inputs added to the builder are spent. If the leftover is
+    /// too small to pay for the fee of the extra change output it is
+    /// left to the fee instead (dust). If the inputs do not cover the
+    /// outputs and the fee, TxNotEnoughTotalInput is returned.
+    pub fn build_with_change<F: FeeAlgorithm>(
+        mut self,
+        change_addr: &ExtendedAddr,
+        f: &F,
+    ) -> Result<Tx> {
+        let policy = OutputPolicy::One(change_addr.clone());
+        match self.add_output_policy(f, &policy) {
+            Ok(_) | Err(Error::TxOutputPolicyNotEnoughCoins(_)) => {}
+            Err(err) => return Err(err),
+        }
+        self.make_tx()
+    }
+
     /// Calculate the Fee that *need* to be paid for the current state of the builder.alloc
     ///
     /// For the LinearFee, it is related to the number of bytes that the representant
@@ -264,9 +300,13 @@ impl TxBuilder {
 
     fn make_tx_nocheck(self) -> Tx {
         let inputs = self.inputs.iter().map(|(v, _)| v.clone()).collect();
-        Tx::new_with(inputs, self.outputs)
+        let mut tx = Tx::new_with(inputs, self.outputs);
+        *tx.attributes_mut() = self.attributes;
+        tx
     }
 
+    /// Create the transaction, checking it has at least one input and
+    /// one output and that none of the outputs has a zero value.
     pub fn make_tx(self) -> Result<Tx> {
         if self.inputs.len() == 0 {
             return Err(Error::TxInvalidNoInput);
@@ -274,6 +314,9 @@ impl TxBuilder {
         if self.outputs.len() == 0 {
             return Err(Error::TxInvalidNoOutput);
         }
+        if let Some(idx) = self.outputs.iter().position(|o| o.value == Coin::zero()) {
+            return Err(Error::TxInvalidZeroOutput(idx));
+        }
         Ok(self.make_tx_nocheck())
     }
 }
@@ -325,7 +368,6 @@ mod tests {
     use super::*;
     use address::ExtendedAddr;
     use fee::LinearFee;
-    use hash::Blake2b256;
     use tx::{TxId, TxOut};
     use util::{base58, try_from_slice::TryFromSlice};
 
@@ -397,7 +439,7 @@ mod tests {
     }
 
     fn fake_id() -> TxId {
-        Blake2b256::new(&[1, 2])
+        TxId::new(&[1, 2])
     }
     fn fake_txopointer_val(coin: Coin) -> (TxoPointer, Coin) {
         (TxoPointer::new(fake_id(), 1), coin)
@@ -411,6 +453,108 @@ mod tests {
         assert!(res.is_ok())
     }
 
+    #[test]
+    fn txbuild_make_tx() {
+        let inputs = vec![fake_txopointer_val(100000u32.into())];
+        let outputs = vec![TxOut::new(decode_addr(RADDRS[1]), 8000u32.into())];
+        let mut attributes = TxAttributes::new();
+        attributes.insert(1, vec![0x00]).unwrap();
+        let mut builder = build_input_outputs(&inputs[..], &outputs[..]);
+        builder.set_attributes(attributes.clone());
+
+        let tx = builder.make_tx().unwrap();
+        assert_eq!(tx.inputs, vec![inputs[0].0.clone()]);
+        assert_eq!(tx.outputs, outputs);
+        assert_eq!(tx.attributes(), &attributes);
+    }
+
+    #[test]
+    fn txbuild_invalid() {
+        let inputs = vec![fake_txopointer_val(100000u32.into())];
+        let outputs = vec![TxOut::new(decode_addr(RADDRS[1]), 8000u32.into())];
+
+        match build_input_outputs(&[], &outputs[..]).make_tx() {
+            Err(Error::TxInvalidNoInput) => {}
+            r => panic!("expected TxInvalidNoInput, got {:?}", r),
+        }
+        match build_input_outputs(&inputs[..], &[]).make_tx() {
+            Err(Error::TxInvalidNoOutput) => {}
+            r => panic!("expected TxInvalidNoOutput, got {:?}", r),
+        }
+
+        let mut outputs = outputs;
+        outputs.push(TxOut::new(decode_addr(RADDRS[2]), Coin::zero()));
+        match build_input_outputs(&inputs[..], &outputs[..]).make_tx() {
+            Err(Error::TxInvalidZeroOutput(1)) => {}
+            r => panic!("expected TxInvalidZeroOutput, got {:?}", r),
+        }
+    }
+
+    // inputs paying exactly for the output and its fee, plus `extra`
+    fn exact_inputs(output: &TxOut, extra: u32) -> Vec<(TxoPointer, Coin)> {
+        let alg = LinearFee::default();
+        let builder = build_input_outputs(&[fake_txopointer_val(Coin::zero())], &[output.clone()]);
+        let fee = builder.calculate_fee(&alg).unwrap().to_coin();
+        let value = ((output.value + fee).unwrap() + Coin::from(extra)).unwrap();
+        vec![fake_txopointer_val(value)]
+    }
+
+    #[test]
+    fn txbuild_with_change() {
+        let alg = LinearFee::default();
+        let change_addr = decode_addr(RADDRS[2]);
+        let inputs = vec![fake_txopointer_val(1_000_000u32.into())];
+        let outputs = vec![TxOut::new(decode_addr(RADDRS[1]), 100_000u32.into())];
+
+        let builder = build_input_outputs(&inputs[..], &outputs[..]);
+        let tx = builder.build_with_change(&change_addr, &alg).unwrap();
+        assert_eq!(tx.outputs.len(), 2);
+        assert_eq!(tx.outputs[1].address, change_addr);
+
+        let builder = build_input_outputs(&inputs[..], &tx.outputs[..]);
+        fee_is_minimal(builder.balance(&alg).unwrap());
+    }
+
+    #[test]
+    fn txbuild_with_change_exact() {
+        let alg = LinearFee::default();
+        let outputs = vec![TxOut::new(decode_addr(RADDRS[1]), 100_000u32.into())];
+        let inputs = exact_inputs(&outputs[0], 0);
+
+        let builder = build_input_outputs(&inputs[..], &outputs[..]);
+        let tx = builder
+            .build_with_change(&decode_addr(RADDRS[2]), &alg)
+            .unwrap();
+        assert_eq!(tx.outputs, outputs);
+    }
+
+    #[test]
+    fn txbuild_with_change_dust() {
+        let alg = LinearFee::default();
+        let outputs = vec![TxOut::new(decode_addr(RADDRS[1]), 100_000u32.into())];
+        let inputs = exact_inputs(&outputs[0], 10);
+
+        let builder = build_input_outputs(&inputs[..], &outputs[..]);
+        let tx = builder
+            .build_with_change(&decode_addr(RADDRS[2]), &alg)
+            .unwrap();
+        // the 10 coins left over are not worth a change output
+        assert_eq!(tx.outputs, outputs);
+    }
+
+    #[test]
+    fn txbuild_with_change_not_enough_input() {
+        let alg = LinearFee::default();
+        let inputs = vec![fake_txopointer_val(100_000u32.into())];
+        let outputs = vec![TxOut::new(decode_addr(RADDRS[1]), 100_000u32.into())];
+
+        let builder = build_input_outputs(&inputs[..], &outputs[..]);
+        match builder.build_with_change(&decode_addr(RADDRS[2]), &alg) {
+            Err(Error::TxNotEnoughTotalInput) => {}
+            r => panic!("expected TxNotEnoughTotalInput, got {:?}", r),
+        }
+    }
+
     #[test]
     fn txbuild_auto() {
         let inputs = vec![fake_txopointer_val(300000u32.into())];
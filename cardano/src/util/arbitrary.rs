@@ -105,6 +105,13 @@ impl Arbitrary for Wrapper<hash::Blake2b256> {
     }
 }
 
+impl Arbitrary for Wrapper<tx::TxId> {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let hash: Wrapper<hash::Blake2b256> = Arbitrary::arbitrary(g);
+        Wrapper(tx::TxId::from(hash.unwrap()))
+    }
+}
+
 impl Arbitrary for Wrapper<tx::TxoPointer> {
     fn arbitrary<G: Gen>(g: &mut G) -> Self {
         let txid: Wrapper<tx::TxId> = Arbitrary::arbitrary(g);
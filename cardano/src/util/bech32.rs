@@ -0,0 +1,314 @@
+//! bech32 encoding format, as specified in BIP173
+//!
+//! A bech32 string is made of a human readable part (`hrp`), the
+//! separator `1` and the data part: the payload converted to 5-bit
+//! groups, followed by a 6 characters checksum.
+//!
+//! # Example
+//!
+//! ```
+//! use cardano::util::bech32;
+//!
+//! let encoded = bech32::encode("addr", b"some bytes").unwrap();
+//! let (hrp, decoded) = bech32::decode(&encoded).unwrap();
+//!
+//! assert_eq!(hrp, "addr");
+//! assert_eq!(decoded.as_slice(), b"some bytes");
+//! ```
+//!
+use std::{fmt, result};
+
+const CHARSET: &'static [u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const SEPARATOR: u8 = b'1';
+const CHECKSUM_SIZE: usize = 6;
+const MAX_HRP_SIZE: usize = 83;
+const MAX_SIZE: usize = 90;
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+/// bech32 encoding/decoding potential errors
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "generic-serialization", derive(Serialize, Deserialize))]
+pub enum Error {
+    /// the human readable part is empty, too long or contains
+    /// characters out of the printable US-ASCII range
+    InvalidHrp,
+    /// the encoded string is longer than the 90 characters allowed or
+    /// too short to contain the checksum. Contains the length.
+    InvalidLength(usize),
+    /// the `1` separating the human readable part from the data is missing
+    MissingSeparator,
+    /// the string mixes upper case and lower case characters
+    MixedCase,
+    /// error when a given character of the data part is not part of
+    /// the bech32 alphabet. Contains the index of the faulty byte.
+    UnknownSymbol(usize),
+    /// the checksum does not match the human readable part and the data
+    InvalidChecksum,
+    /// the data part does not convert back to whole bytes
+    InvalidPadding,
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &Error::InvalidHrp => write!(f, "Invalid human readable part"),
+            &Error::InvalidLength(len) => write!(f, "Invalid bech32 string length {}", len),
+            &Error::MissingSeparator => write!(f, "Missing separator"),
+            &Error::MixedCase => write!(f, "Mixed upper and lower case"),
+            &Error::UnknownSymbol(idx) => write!(f, "Unknown symbol at byte index {}", idx),
+            &Error::InvalidChecksum => write!(f, "Invalid checksum"),
+            &Error::InvalidPadding => write!(f, "Invalid padding in the data part"),
+        }
+    }
+}
+impl ::std::error::Error for Error {}
+
+pub type Result<T> = result::Result<T, Error>;
+
+fn polymod(values: &[u8]) -> u32 {
+    let mut chk: u32 = 1;
+    for &v in values {
+        let b = chk >> 25;
+        chk = (chk & 0x1ffffff) << 5 ^ (v as u32);
+        for (i, gen) in GENERATOR.iter().enumerate() {
+            if (b >> i) & 1 == 1 {
+                chk ^= gen;
+            }
+        }
+    }
+    chk
+}
+
+fn hrp_expand(hrp: &[u8]) -> Vec<u8> {
+    let mut v = Vec::with_capacity(hrp.len() * 2 + 1);
+    v.extend(hrp.iter().map(|c| c >> 5));
+    v.push(0);
+    v.extend(hrp.iter().map(|c| c & 0x1f));
+    v
+}
+
+fn create_checksum(hrp: &[u8], data: &[u8]) -> [u8; CHECKSUM_SIZE] {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0; CHECKSUM_SIZE]);
+    let pm = polymod(&values) ^ 1;
+
+    let mut checksum = [0; CHECKSUM_SIZE];
+    for (i, c) in checksum.iter_mut().enumerate() {
+        *c = ((pm >> (5 * (5 - i))) & 0x1f) as u8;
+    }
+    checksum
+}
+
+fn verify_checksum(hrp: &[u8], data: &[u8]) -> bool {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    polymod(&values) == 1
+}
+
+// regroup the bits of `input`, `from` bits at a time, in groups of
+// `to` bits. When converting back to bytes (`pad == false`) the left
+// over bits must be less than a group and all zeros.
+fn convert_bits(input: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let mut out = Vec::with_capacity(input.len() * from as usize / to as usize + 1);
+    let maxv: u32 = (1 << to) - 1;
+
+    for &value in input {
+        acc = (acc << from) | (value as u32);
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & maxv) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (to - bits)) & maxv) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & maxv) != 0 {
+        return Err(Error::InvalidPadding);
+    }
+    Ok(out)
+}
+
+fn check_hrp(hrp: &[u8]) -> Result<()> {
+    if hrp.is_empty() || hrp.len() > MAX_HRP_SIZE {
+        return Err(Error::InvalidHrp);
+    }
+    if hrp.iter().any(|c| !(33..=126).contains(c)) {
+        return Err(Error::InvalidHrp);
+    }
+    Ok(())
+}
+
+/// encode the given data in bech32 with the given human readable part
+///
+/// # Example
+///
+/// ```
+/// use cardano::util::bech32;
+///
+/// assert_eq!(bech32::encode("a", &[]).unwrap(), "a12uel5l");
+/// ```
+pub fn encode(hrp: &str, data: &[u8]) -> Result<String> {
+    let hrp = hrp.as_bytes();
+    check_hrp(hrp)?;
+    let has_lower = hrp.iter().any(|c| c.is_ascii_lowercase());
+    let has_upper = hrp.iter().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return Err(Error::MixedCase);
+    }
+    let hrp = hrp.to_ascii_lowercase();
+
+    let data = convert_bits(data, 8, 5, true)?;
+    let len = hrp.len() + 1 + data.len() + CHECKSUM_SIZE;
+    if len > MAX_SIZE {
+        return Err(Error::InvalidLength(len));
+    }
+    let checksum = create_checksum(&hrp, &data);
+
+    let mut v = Vec::with_capacity(len);
+    v.extend_from_slice(&hrp);
+    v.push(SEPARATOR);
+    v.extend(
+        data.iter()
+            .chain(checksum.iter())
+            .map(|&d| CHARSET[d as usize]),
+    );
+
+    // the human readable part is checked to be printable ASCII and the
+    // data part only contains bytes of the ASCII `CHARSET`
+    Ok(String::from_utf8(v).unwrap())
+}
+
+/// decode the given bech32 string, returns the human readable part
+/// (in lower case) and the decoded data
+///
+/// # Example
+///
+/// ```
+/// use cardano::util::bech32;
+///
+/// let (hrp, data) = bech32::decode("A12UEL5L").unwrap();
+/// assert_eq!(hrp, "a");
+/// assert!(data.is_empty());
+/// ```
+pub fn decode(input: &str) -> Result<(String, Vec<u8>)> {
+    let input = input.as_bytes();
+    if input.len() > MAX_SIZE {
+        return Err(Error::InvalidLength(input.len()));
+    }
+    let has_lower = input.iter().any(|c| c.is_ascii_lowercase());
+    let has_upper = input.iter().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return Err(Error::MixedCase);
+    }
+    let input = input.to_ascii_lowercase();
+
+    let sep = match input.iter().rposition(|&c| c == SEPARATOR) {
+        None => return Err(Error::MissingSeparator),
+        Some(sep) => sep,
+    };
+    let (hrp, data_part) = (&input[..sep], &input[sep + 1..]);
+    check_hrp(hrp)?;
+    if data_part.len() < CHECKSUM_SIZE {
+        return Err(Error::InvalidLength(input.len()));
+    }
+
+    let mut data = Vec::with_capacity(data_part.len());
+    for (idx, byte) in data_part.iter().enumerate() {
+        match CHARSET.iter().position(|c| c == byte) {
+            None => return Err(Error::UnknownSymbol(sep + 1 + idx)),
+            Some(d) => data.push(d as u8),
+        }
+    }
+    if !verify_checksum(hrp, &data) {
+        return Err(Error::InvalidChecksum);
+    }
+    let data_len = data.len() - CHECKSUM_SIZE;
+    let bytes = convert_bits(&data[..data_len], 5, 8, false)?;
+
+    // `check_hrp` only accepts ASCII characters
+    Ok((String::from_utf8(hrp.to_vec()).unwrap(), bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::hex;
+
+    fn roundtrip(hrp: &str, data: &str, expected: &str) {
+        let data = hex::decode(data).unwrap();
+        assert_eq!(encode(hrp, &data).unwrap(), expected);
+        assert_eq!(decode(expected).unwrap(), (hrp.to_lowercase(), data));
+    }
+
+    fn invalid(input: &str, err: Error) {
+        assert_eq!(decode(input), Err(err));
+    }
+
+    #[test]
+    fn valid_checksums() {
+        roundtrip("a", "", "a12uel5l");
+        roundtrip(
+            "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio",
+            "",
+            "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+        );
+        roundtrip(
+            "abcdef",
+            "00443214c74254b635cf84653a56d7c675be77df",
+            "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+        );
+        roundtrip(
+            "1",
+            &"00".repeat(51),
+            "11qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqc8247j",
+        );
+        roundtrip(
+            "split",
+            "c5f38b70305f519bf66d85fb6cf03058f3dde463ecd7918f2dc743918f2d",
+            "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+        );
+        roundtrip("?", "", "?1ezyfcl");
+    }
+
+    #[test]
+    fn decode_upper_case() {
+        assert_eq!(decode("A12UEL5L"), Ok(("a".to_string(), vec![])));
+        assert_eq!(encode("A", &[]).unwrap(), "a12uel5l");
+    }
+
+    #[test]
+    fn invalid_strings() {
+        invalid(" 1nwldj5", Error::InvalidHrp);
+        invalid("\x7f1axkwrx", Error::InvalidHrp);
+        invalid("\u{80}1eym55h", Error::InvalidHrp);
+        invalid(
+            "an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx",
+            Error::InvalidLength(91),
+        );
+        invalid("pzry9x0s0muk", Error::MissingSeparator);
+        invalid("1pzry9x0s0muk", Error::InvalidHrp);
+        invalid("x1b4n0q5v", Error::UnknownSymbol(2));
+        invalid("li1dgmt3", Error::InvalidLength(8));
+        invalid("de1lg7wt\u{ff}", Error::UnknownSymbol(8));
+        invalid("10a06t8", Error::InvalidHrp);
+        invalid("1qzzfhee", Error::InvalidHrp);
+        invalid("a12UEL5L", Error::MixedCase);
+    }
+
+    #[test]
+    fn invalid_checksum() {
+        invalid("A1G7SGD8", Error::InvalidChecksum);
+        invalid("a12uel5m", Error::InvalidChecksum);
+    }
+
+    #[test]
+    fn invalid_hrp_on_encode() {
+        assert_eq!(encode("", &[]), Err(Error::InvalidHrp));
+        assert_eq!(encode("aB", &[]), Err(Error::MixedCase));
+    }
+}
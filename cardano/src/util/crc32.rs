@@ -1,3 +1,14 @@
+//! CRC32 (IEEE 802.3 polynomial) as used in the checksum of the
+//! addresses
+//!
+//! # Example
+//!
+//! ```
+//! use cardano::util::crc32::crc32;
+//!
+//! assert_eq!(crc32(b"123456789"), 0xcbf43926);
+//! ```
+//!
 const CRC_TABLE: [u32; 256] = [
     0x00000000u32,
     0x77073096u32,
@@ -304,8 +315,8 @@ impl ::std::io::Write for Crc32 {
     }
 }
 
-/// function is kept for compatibility. however prefer the
-/// `Crc32` structure.
+/// compute the CRC32 of the given bytes in one go. Use the `Crc32`
+/// structure to compute it over a stream.
 ///
 pub fn crc32(input: &[u8]) -> u32 {
     let mut crc32 = Crc32::new();
@@ -320,4 +331,12 @@ mod tests {
         let s = b"The quick brown fox jumps over the lazy dog";
         assert_eq!(0x414fa339, super::crc32(s));
     }
+
+    #[test]
+    fn crc32_streamed() {
+        let mut crc32 = super::Crc32::new();
+        crc32.update(b"12345".iter()).update(b"6789".iter());
+        assert_eq!(0xcbf43926, crc32.finalize());
+        assert_eq!(0, super::crc32(&[]));
+    }
 }
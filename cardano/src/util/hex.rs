@@ -21,11 +21,15 @@ pub enum Error {
     /// error when a given character is not part of the supported
     /// hexadecimal alphabet. Contains the index of the faulty byte
     UnknownSymbol(usize),
+    /// error when the input does not contain an even number of
+    /// hexadecimal digits: the last byte is incomplete.
+    OddLength,
 }
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             &Error::UnknownSymbol(idx) => write!(f, "Unknown symbol at byte index {}", idx),
+            &Error::OddLength => write!(f, "Odd number of hexadecimal digits"),
         }
     }
 }
@@ -93,6 +97,9 @@ pub fn decode(input: &str) -> Result<Vec<u8>> {
         }
     }
 
+    if modulus != 0 {
+        return Err(Error::OddLength);
+    }
     Ok(b)
 }
 
@@ -118,4 +125,20 @@ mod tests {
         encode(&[0xff, 0x0f, 0xff, 0xff], "ff0fffff");
         decode(&[0xff, 0x0f, 0xff, 0xff], "ff0fffff");
     }
+
+    #[test]
+    fn decode_upper_case() {
+        decode(&[0xff, 0x0f, 0xab, 0xcd], "FF0FABCD");
+        decode(&[0xff, 0x0f, 0xab, 0xcd], "Ff0fAbcD");
+    }
+
+    #[test]
+    fn decode_odd_length() {
+        assert_eq!(super::decode("01020"), Err(super::Error::OddLength));
+    }
+
+    #[test]
+    fn decode_unknown_symbol() {
+        assert_eq!(super::decode("0102g4"), Err(super::Error::UnknownSymbol(4)));
+    }
 }
@@ -1,7 +1,10 @@
 pub mod base58;
+pub mod bech32;
 pub mod bits;
+pub mod crc32;
 pub mod diff_maps;
 pub mod hex;
+pub mod secret;
 pub mod securemem;
 pub mod try_from_slice;
 
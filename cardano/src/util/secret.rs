@@ -0,0 +1,112 @@
+//! Wrapper for secret values (private keys, seeds...)
+//!
+//! `Secret` does not print the value it holds, neither with `Debug`
+//! nor with `Display`, so deriving `Debug` on a structure holding
+//! a private key does not leak it in the logs. The value has to be
+//! explicitly accessed with `expose`. Secrets are compared in
+//! constant time.
+//!
+//! # Example
+//!
+//! ```
+//! use cardano::util::secret::Secret;
+//!
+//! let secret = Secret::new(String::from("my private key"));
+//! assert_eq!(format!("{:?}", secret), "<redacted>");
+//! assert_eq!(secret.expose(), "my private key");
+//! ```
+//!
+use cryptoxide::util::fixed_time_eq;
+use std::fmt;
+
+#[derive(Clone)]
+pub struct Secret<T>(T);
+impl<T> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Secret(value)
+    }
+
+    /// access the secret value
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// mutably access the secret value
+    pub fn expose_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+
+    /// retrieve the secret value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Secret::new(value)
+    }
+}
+/// constant time comparison: the time taken does not depend on the
+/// content of the secrets, only on their length
+impl<T: AsRef<[u8]>> PartialEq for Secret<T> {
+    fn eq(&self, other: &Self) -> bool {
+        let (lhs, rhs) = (self.0.as_ref(), other.0.as_ref());
+        lhs.len() == rhs.len() && fixed_time_eq(lhs, rhs)
+    }
+}
+impl<T: AsRef<[u8]>> Eq for Secret<T> {}
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<redacted>")
+    }
+}
+impl<T> fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<redacted>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hdwallet::{XPrv, XPRV_SIZE};
+    use redeem;
+    use util::hex;
+
+    #[test]
+    fn secret_is_not_printed() {
+        let xprv = XPrv::normalize_bytes([0x42; XPRV_SIZE]);
+        let key_hex = hex::encode(xprv.as_ref());
+        let secret = Secret::new(xprv.clone());
+
+        let debug = format!("{:?}", secret);
+        let display = format!("{}", secret);
+        assert!(!debug.contains(&key_hex));
+        assert!(!display.contains(&key_hex));
+        assert!(!debug.contains(&key_hex[..16]));
+        assert_eq!(debug, "<redacted>");
+
+        assert_eq!(secret.expose(), &xprv);
+        assert_eq!(secret.into_inner(), xprv);
+    }
+
+    #[test]
+    fn secret_eq() {
+        let secret = Secret::new(vec![1, 2, 3]);
+        assert_eq!(secret, Secret::new(vec![1, 2, 3]));
+        assert_ne!(secret, Secret::new(vec![1, 2, 4]));
+        assert_ne!(secret, Secret::new(vec![1, 2]));
+        assert_ne!(secret, Secret::new(vec![]));
+    }
+
+    #[test]
+    fn private_keys_are_not_printed() {
+        let xprv = XPrv::normalize_bytes([0x42; XPRV_SIZE]);
+        let debug = format!("{:?}", xprv);
+        assert!(!debug.contains(&hex::encode(xprv.as_ref())[..16]));
+
+        let key = redeem::PrivateKey::generate(&[0x42; redeem::PRIVATEKEY_SIZE]).unwrap();
+        let debug = format!("{:?}", key);
+        assert!(!debug.contains(&hex::encode(key.as_ref())[..16]));
+    }
+}
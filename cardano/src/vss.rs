@@ -1,4 +1,5 @@
 use cbor_event::{self, de::Deserializer, se::Serializer};
+use cryptoxide::util::fixed_time_eq;
 use std::{
     fmt,
     io::{BufRead, Write},
@@ -88,6 +89,14 @@ impl AsRef<[u8]> for Signature {
         &self.0
     }
 }
+/// constant time comparison: the time taken does not depend on the
+/// content of the signatures
+impl PartialEq for Signature {
+    fn eq(&self, other: &Self) -> bool {
+        fixed_time_eq(&self.0, &other.0)
+    }
+}
+impl Eq for Signature {}
 impl cbor_event::se::Serialize for Signature {
     fn serialize<'se, W: Write>(
         &self,
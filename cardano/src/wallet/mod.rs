@@ -2,3 +2,4 @@ pub mod bip44;
 pub mod keygen;
 pub mod rindex;
 pub mod scheme;
+pub mod secure;
@@ -14,6 +14,7 @@ use input_selection;
 use std::{error, fmt, iter, ops::Deref};
 use tx::{self, Tx, TxAux, TxId, TxInWitness};
 use txutils::{self, OutputPolicy};
+use util::secret::Secret;
 
 use super::scheme;
 
@@ -338,15 +339,15 @@ impl error::Error for Error {
 
 pub type Result<T> = ::std::result::Result<T, Error>;
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct RootKey {
-    root_key: XPrv,
+    root_key: Secret<XPrv>,
     derivation_scheme: DerivationScheme,
 }
 impl RootKey {
     pub fn new(root_key: XPrv, derivation_scheme: DerivationScheme) -> Self {
         RootKey {
-            root_key,
+            root_key: Secret::new(root_key),
             derivation_scheme,
         }
     }
@@ -379,17 +380,17 @@ impl RootKey {
 
     /// Converts into the inner `XPrv` value
     pub fn into_xprv(self) -> XPrv {
-        self.root_key
+        self.root_key.into_inner()
     }
 
     pub fn address_generator(&self) -> AddressGenerator<XPrv> {
-        AddressGenerator::<XPrv>::new(self.root_key.clone(), self.derivation_scheme)
+        AddressGenerator::<XPrv>::new(self.root_key.expose().clone(), self.derivation_scheme)
     }
 }
 impl Deref for RootKey {
     type Target = XPrv;
     fn deref(&self) -> &Self::Target {
-        &self.root_key
+        self.root_key.expose()
     }
 }
 
@@ -666,4 +667,18 @@ mod test {
             assert!(witness.verify_tx(*PROTOCOL_MAGIC, &txaux.tx));
         }
     }
+
+    #[test]
+    fn test_root_key_debug_is_redacted() {
+        let root_key = RootKey::from_daedalus_mnemonics(
+            DerivationScheme::V1,
+            &bip39::dictionary::ENGLISH,
+            MNEMONICS,
+        )
+        .unwrap();
+        let key_hex = format!("{}", *root_key);
+        let debug = format!("{:?}", root_key);
+        assert!(!debug.contains(&key_hex));
+        assert!(debug.contains("<redacted>"));
+    }
 }
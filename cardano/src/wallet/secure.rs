@@ -0,0 +1,177 @@
+//! Passphrase protection of the wallet's root private key
+//!
+//! The passphrase is stretched with PBKDF2 (HMAC-SHA512) and the
+//! resulting key is used to auth-encrypt the `XPrv` with
+//! chacha20poly1305. The salt and the nonce are freshly generated on
+//! every encryption and stored in front of the encrypted key:
+//!
+//! ```text
+//! | salt (16 bytes) | nonce (12 bytes) | encrypted XPrv (96 bytes) | tag (16 bytes) |
+//! ```
+//!
+use cryptoxide::chacha20poly1305::ChaCha20Poly1305;
+use cryptoxide::hmac::Hmac;
+use cryptoxide::pbkdf2::pbkdf2;
+use cryptoxide::sha2::Sha512;
+
+use std::fmt;
+
+use hdwallet::{self, XPrv, XPRV_SIZE};
+use rand::{CryptoRng, RngCore};
+use util::securemem;
+
+const ITERS: u32 = 10000;
+const SALT_SIZE: usize = 16;
+const NONCE_SIZE: usize = 12;
+const KEY_SIZE: usize = 32;
+const TAG_SIZE: usize = 16;
+
+/// size of the blob returned by [`encrypt_xprv`](./fn.encrypt_xprv.html)
+pub const ENCRYPTED_XPRV_SIZE: usize = SALT_SIZE + NONCE_SIZE + XPRV_SIZE + TAG_SIZE;
+
+#[derive(Debug)]
+pub enum DecryptError {
+    /// the encrypted blob does not have the expected size
+    InvalidSize(usize),
+    /// the authentication tag does not match: the passphrase is
+    /// not the one used to encrypt the key (or the blob was altered)
+    BadPassphrase,
+    /// the blob decrypted fine but does not contain a valid `XPrv`
+    InvalidKey(hdwallet::Error),
+}
+impl fmt::Display for DecryptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &DecryptError::InvalidSize(sz) => write!(
+                f,
+                "Invalid encrypted key size, expected {} bytes, received {} bytes",
+                ENCRYPTED_XPRV_SIZE, sz
+            ),
+            &DecryptError::BadPassphrase => write!(f, "Cannot decrypt key with given passphrase"),
+            &DecryptError::InvalidKey(_) => write!(f, "Decrypted key is not a valid private key"),
+        }
+    }
+}
+impl ::std::error::Error for DecryptError {
+    fn cause(&self) -> Option<&::std::error::Error> {
+        match self {
+            &DecryptError::InvalidKey(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+fn stretch(salt: &[u8], passphrase: &[u8], key: &mut [u8; KEY_SIZE]) {
+    let mut mac = Hmac::new(Sha512::new(), passphrase);
+    pbkdf2(&mut mac, salt, ITERS, key);
+}
+
+/// encrypt the given `XPrv` with the passphrase.
+///
+/// `rng` is used to generate the random salt and nonce, a new salt
+/// and nonce being drawn for every call.
+///
+/// ```
+/// extern crate rand;
+/// # extern crate cardano;
+/// # use cardano::hdwallet::{XPrv, XPRV_SIZE};
+/// # use cardano::wallet::secure::{encrypt_xprv, decrypt_xprv};
+///
+/// # let xprv = XPrv::normalize_bytes([1; XPRV_SIZE]);
+/// let blob = encrypt_xprv(&xprv, b"passphrase", &mut rand::rngs::OsRng::new().unwrap());
+/// assert_eq!(decrypt_xprv(&blob, b"passphrase").unwrap(), xprv);
+/// ```
+pub fn encrypt_xprv<R>(xprv: &XPrv, passphrase: &[u8], rng: &mut R) -> Vec<u8>
+where
+    R: RngCore + CryptoRng,
+{
+    let mut out = vec![0; ENCRYPTED_XPRV_SIZE];
+    rng.fill_bytes(&mut out[0..SALT_SIZE + NONCE_SIZE]);
+
+    let mut key = [0; KEY_SIZE];
+    stretch(&out[0..SALT_SIZE], passphrase, &mut key);
+
+    let (header, body) = out.split_at_mut(SALT_SIZE + NONCE_SIZE);
+    let (encrypted, tag) = body.split_at_mut(XPRV_SIZE);
+    let mut ctx = ChaCha20Poly1305::new(&key, &header[SALT_SIZE..], &[]);
+    ctx.encrypt(xprv.as_ref(), encrypted, tag);
+
+    securemem::zero(&mut key);
+    out
+}
+
+/// decrypt a blob created with [`encrypt_xprv`](./fn.encrypt_xprv.html)
+pub fn decrypt_xprv(blob: &[u8], passphrase: &[u8]) -> Result<XPrv, DecryptError> {
+    if blob.len() != ENCRYPTED_XPRV_SIZE {
+        return Err(DecryptError::InvalidSize(blob.len()));
+    }
+    let salt = &blob[0..SALT_SIZE];
+    let nonce = &blob[SALT_SIZE..SALT_SIZE + NONCE_SIZE];
+    let encrypted = &blob[SALT_SIZE + NONCE_SIZE..SALT_SIZE + NONCE_SIZE + XPRV_SIZE];
+    let tag = &blob[SALT_SIZE + NONCE_SIZE + XPRV_SIZE..];
+
+    let mut key = [0; KEY_SIZE];
+    stretch(salt, passphrase, &mut key);
+
+    let mut ctx = ChaCha20Poly1305::new(&key, nonce, &[]);
+    let mut bytes = [0; XPRV_SIZE];
+    let authenticated = ctx.decrypt(encrypted, &mut bytes, tag);
+    securemem::zero(&mut key);
+
+    if !authenticated {
+        securemem::zero(&mut bytes);
+        return Err(DecryptError::BadPassphrase);
+    }
+    XPrv::from_bytes_verified(bytes).map_err(DecryptError::InvalidKey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand;
+
+    fn xprv() -> XPrv {
+        XPrv::normalize_bytes([0x42; XPRV_SIZE])
+    }
+
+    #[test]
+    fn encrypt_decrypt_roundtrip() {
+        let xprv = xprv();
+        let blob = encrypt_xprv(&xprv, b"my secret passphrase", &mut rand::thread_rng());
+        assert_eq!(blob.len(), ENCRYPTED_XPRV_SIZE);
+        assert_eq!(decrypt_xprv(&blob, b"my secret passphrase").unwrap(), xprv);
+    }
+
+    #[test]
+    fn decrypt_with_wrong_passphrase() {
+        let blob = encrypt_xprv(&xprv(), b"my secret passphrase", &mut rand::thread_rng());
+        match decrypt_xprv(&blob, b"not my passphrase") {
+            Err(DecryptError::BadPassphrase) => {}
+            r => panic!("expected BadPassphrase, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn decrypt_invalid_size() {
+        let blob = encrypt_xprv(&xprv(), b"passphrase", &mut rand::thread_rng());
+        match decrypt_xprv(&blob[1..], b"passphrase") {
+            Err(DecryptError::InvalidSize(sz)) => assert_eq!(sz, ENCRYPTED_XPRV_SIZE - 1),
+            r => panic!("expected InvalidSize, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn encrypt_twice_uses_new_salt_and_nonce() {
+        let xprv = xprv();
+        let blob1 = encrypt_xprv(&xprv, b"passphrase", &mut rand::thread_rng());
+        let blob2 = encrypt_xprv(&xprv, b"passphrase", &mut rand::thread_rng());
+        assert_ne!(blob1[..SALT_SIZE], blob2[..SALT_SIZE]);
+        assert_ne!(
+            blob1[SALT_SIZE..SALT_SIZE + NONCE_SIZE],
+            blob2[SALT_SIZE..SALT_SIZE + NONCE_SIZE]
+        );
+        assert_ne!(blob1, blob2);
+        assert_eq!(decrypt_xprv(&blob1, b"passphrase").unwrap(), xprv);
+        assert_eq!(decrypt_xprv(&blob2, b"passphrase").unwrap(), xprv);
+    }
+}
@@ -0,0 +1,165 @@
+//! In memory cache of decoded blocks
+//!
+//! Decoding a block is costly compared to reading it from the disk,
+//! the `BlockCache` keeps the most recently used decoded blocks around
+//! so the same block is not decoded again and again.
+
+use cardano::block::{Block, HeaderHash, RawBlock};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use Result;
+
+/// A size bounded cache of decoded blocks, keyed by their header hash.
+///
+/// Once `capacity` blocks are cached, the least recently used one is
+/// evicted to make room for a new one. The cache can be shared between
+/// threads.
+pub struct BlockCache {
+    capacity: usize,
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    // the blocks and the generation they were last used at
+    blocks: HashMap<HeaderHash, (u64, Arc<Block>)>,
+    // the cached hashes by generation, the least recently used first
+    usage: BTreeMap<u64, HeaderHash>,
+    generation: u64,
+}
+
+impl Inner {
+    fn get(&mut self, hash: &HeaderHash) -> Option<Arc<Block>> {
+        let generation = self.generation;
+        let entry = self.blocks.get_mut(hash)?;
+        let last_used = ::std::mem::replace(&mut entry.0, generation);
+        let blk = entry.1.clone();
+
+        self.usage.remove(&last_used);
+        self.usage.insert(generation, hash.clone());
+        self.generation += 1;
+        Some(blk)
+    }
+
+    fn insert(&mut self, capacity: usize, hash: HeaderHash, blk: Arc<Block>) {
+        if self.get(&hash).is_some() || capacity == 0 {
+            return;
+        }
+        while self.blocks.len() >= capacity {
+            let lru = match self.usage.keys().next() {
+                None => break,
+                Some(generation) => *generation,
+            };
+            if let Some(lru) = self.usage.remove(&lru) {
+                self.blocks.remove(&lru);
+            }
+        }
+        self.usage.insert(self.generation, hash.clone());
+        self.blocks.insert(hash, (self.generation, blk));
+        self.generation += 1;
+    }
+}
+
+impl BlockCache {
+    /// create an empty cache holding at most `capacity` blocks. A cache
+    /// of capacity 0 does not keep any block.
+    pub fn new(capacity: usize) -> Self {
+        BlockCache {
+            capacity,
+            inner: Mutex::new(Inner {
+                blocks: HashMap::with_capacity(capacity),
+                usage: BTreeMap::new(),
+                generation: 0,
+            }),
+        }
+    }
+
+    /// the number of blocks currently in the cache
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// get the block of the given `hash` from the cache, or decode it
+    /// from `bytes` (the raw block) and add it to the cache.
+    ///
+    /// `bytes` are not decoded nor checked against `hash` if the block
+    /// is already cached.
+    pub fn get_or_decode(&self, hash: &HeaderHash, bytes: &[u8]) -> Result<Arc<Block>> {
+        if let Some(blk) = self.inner.lock().unwrap().get(hash) {
+            return Ok(blk);
+        }
+
+        // decode without holding the lock, so other threads can still
+        // use the cache in the meantime
+        let blk = Arc::new(RawBlock::from_dat(bytes.to_vec()).decode()?);
+
+        self.inner
+            .lock()
+            .unwrap()
+            .insert(self.capacity, hash.clone(), blk.clone());
+        Ok(blk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::boundary_block;
+    use Error;
+
+    #[test]
+    fn cache_hit_does_not_decode() {
+        let blk = boundary_block(HeaderHash::new(b"genesis"), 0);
+        let hash = blk.header_hash();
+        let cache = BlockCache::new(2);
+
+        let decoded = cache.get_or_decode(&hash, &blk.to_raw().0).unwrap();
+        assert_eq!(*decoded, blk);
+        assert_eq!(cache.len(), 1);
+
+        // invalid bytes would fail to decode
+        let cached = cache.get_or_decode(&hash, &[0xff]).unwrap();
+        assert!(Arc::ptr_eq(&decoded, &cached));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used() {
+        let blk1 = boundary_block(HeaderHash::new(b"genesis"), 0);
+        let blk2 = boundary_block(blk1.header_hash(), 1);
+        let blk3 = boundary_block(blk2.header_hash(), 2);
+        let cache = BlockCache::new(2);
+
+        for blk in [&blk1, &blk2].iter() {
+            cache
+                .get_or_decode(&blk.header_hash(), &blk.to_raw().0)
+                .unwrap();
+        }
+        // blk1 is now more recently used than blk2
+        cache.get_or_decode(&blk1.header_hash(), &[]).unwrap();
+        cache
+            .get_or_decode(&blk3.header_hash(), &blk3.to_raw().0)
+            .unwrap();
+        assert_eq!(cache.len(), 2);
+
+        assert!(cache.get_or_decode(&blk1.header_hash(), &[]).is_ok());
+        assert!(cache.get_or_decode(&blk3.header_hash(), &[]).is_ok());
+        match cache.get_or_decode(&blk2.header_hash(), &[]) {
+            Err(Error::CborBlockError(_)) => {}
+            _ => panic!("expected blk2 to be evicted"),
+        }
+    }
+
+    #[test]
+    fn cache_of_no_capacity() {
+        let blk = boundary_block(HeaderHash::new(b"genesis"), 0);
+        let cache = BlockCache::new(0);
+        cache
+            .get_or_decode(&blk.header_hash(), &blk.to_raw().0)
+            .unwrap();
+        assert!(cache.is_empty());
+    }
+}
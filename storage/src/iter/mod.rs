@@ -20,11 +20,60 @@ pub use self::reverse::iter as reverse_iter;
 
 use super::Result;
 
-use super::{BlockLocation, Storage};
+use super::{BlockLocation, Error, Storage, StorageConfig};
 
 use cardano::block::{Block, RawBlock};
 use storage_units::hash::BlockHash;
 
+/// Iterator over the decoded blocks of every epoch pack of the
+/// storage, in order.
+///
+/// A block which cannot be decoded is reported as an error and the
+/// iteration carries on with the next block. An error reading the
+/// packs ends the iteration.
+pub struct Blocks<'a> {
+    epochs: epoch::Epochs<'a>,
+    current: Option<epoch::Iter>,
+    finished: bool,
+}
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Result<Block>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            if let Some(ref mut iter) = self.current {
+                match iter.next() {
+                    Some(Ok(rblk)) => return Some(rblk.decode().map_err(Error::from)),
+                    Some(Err(err)) => {
+                        self.finished = true;
+                        return Some(Err(err));
+                    }
+                    None => {}
+                }
+            }
+            match self.epochs.next() {
+                None => self.finished = true,
+                Some(Ok(iter)) => self.current = Some(iter),
+                Some(Err(err)) => {
+                    self.finished = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Constructs a `Blocks` iterator over the epoch packs of the given
+/// storage, starting from epoch 0.
+pub fn iter_blocks(config: &StorageConfig) -> Blocks {
+    Blocks {
+        epochs: epoch::Epochs::new(config),
+        current: None,
+        finished: false,
+    }
+}
+
 enum IteratorType<'a> {
     Epoch(epoch::Epochs<'a>, Option<epoch::Iter>),
     Loose(&'a Storage, Range),
@@ -208,3 +257,69 @@ impl<'a> Iterator for Iter<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cardano::block::HeaderHash;
+    use cardano::util::hex;
+    use cbor_event::se::Serializer;
+    use pack::{packwriter_finalize, packwriter_init};
+    use rand;
+    use std::{env, fs};
+    use storage_units::utils::tmpfile;
+    use test_utils::boundary_block;
+    use types::header_to_blockhash;
+
+    fn encode(blk: &Block) -> Vec<u8> {
+        let mut se = Serializer::new_vec();
+        se.serialize(blk).unwrap();
+        se.finalize()
+    }
+
+    #[test]
+    fn iter_blocks_of_epoch_pack() {
+        let mut dir = env::temp_dir();
+        dir.push(format!("cardano-storage-{}", rand::random::<u64>()));
+        let config = StorageConfig::new(&dir);
+        Storage::init(&config).unwrap();
+
+        let blk1 = boundary_block(HeaderHash::new(b"genesis"), 0);
+        let blk2 = boundary_block(blk1.header_hash(), 1);
+
+        let mut writer = packwriter_init(&config).unwrap();
+        writer
+            .append(&header_to_blockhash(&blk1.header_hash()), &encode(&blk1))
+            .unwrap();
+        writer.append(&[0; 32], &[0xff]).unwrap();
+        writer
+            .append(&header_to_blockhash(&blk2.header_hash()), &encode(&blk2))
+            .unwrap();
+        let (packhash, _) = packwriter_finalize(&config, writer);
+
+        fs::create_dir_all(config.get_epoch_dir(0)).unwrap();
+        tmpfile::atomic_write_simple(
+            &config.get_epoch_pack_filepath(0),
+            hex::encode(&packhash).as_bytes(),
+        )
+        .unwrap();
+
+        let blocks: Vec<_> = iter_blocks(&config).collect();
+        assert_eq!(blocks.len(), 3);
+        match &blocks[0] {
+            Ok(blk) => assert_eq!(blk.header_hash(), blk1.header_hash()),
+            Err(err) => panic!("cannot read first block: {}", err),
+        }
+        // the invalid block does not stop the iteration
+        match &blocks[1] {
+            Err(Error::CborBlockError(_)) => {}
+            _ => panic!("expected a decoding error"),
+        }
+        match &blocks[2] {
+            Ok(blk) => assert_eq!(blk.header_hash(), blk2.header_hash()),
+            Err(err) => panic!("cannot read second block: {}", err),
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -5,6 +5,7 @@ extern crate cbor_event;
 extern crate rand;
 extern crate storage_units;
 
+pub mod cache;
 pub mod chain_state;
 pub mod config;
 pub mod epoch;
@@ -13,8 +14,13 @@ pub mod pack;
 pub mod refpack;
 pub mod tag;
 pub mod types;
+
+#[cfg(test)]
+mod test_utils;
+
 use std::{fs, io, result};
 
+pub use cache::BlockCache;
 pub use config::StorageConfig;
 
 use cardano::block::{Block, BlockDate, EpochId, HeaderHash, RawBlock, SlotId};
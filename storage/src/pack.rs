@@ -1,6 +1,9 @@
 use super::Result;
 use cardano;
+use cardano::block::{Block, RawBlock};
 use std::fs;
+use std::path::{Path, PathBuf};
+use storage_units::utils::serialize::Offset;
 use storage_units::utils::tmpfile::TmpFile;
 
 use storage_units::indexfile;
@@ -71,3 +74,99 @@ pub fn packreader_block_next(
     let next = reader.next_block()?;
     Ok(next.map(|x| cardano::block::RawBlock(x)))
 }
+
+/// Reader of the blocks stored in a pack file
+pub struct PackReader {
+    path: PathBuf,
+    seeker: packfile::Seeker<fs::File>,
+}
+impl PackReader {
+    /// open the pack file and check its header
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let seeker = packfile::Seeker::init(path.as_ref())?;
+        Ok(PackReader {
+            path: path.as_ref().to_path_buf(),
+            seeker,
+        })
+    }
+
+    /// read and decode the block stored at the given offset (as
+    /// recorded in the pack's index)
+    pub fn get_block_at(&mut self, offset: Offset) -> Result<Block> {
+        let bytes = self.seeker.block_at_offset(offset)?;
+        let block = RawBlock(bytes).decode()?;
+        Ok(block)
+    }
+
+    /// iterate over every block of the pack, in order
+    pub fn iter(&self) -> Result<PackBlocks> {
+        let reader = packfile::Reader::open(&self.path)?;
+        Ok(PackBlocks(reader))
+    }
+}
+
+/// Iterator over the decoded blocks of a pack file
+pub struct PackBlocks(packfile::Reader<fs::File>);
+impl Iterator for PackBlocks {
+    type Item = Result<Block>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match packreader_block_next(&mut self.0) {
+            Ok(None) => None,
+            Ok(Some(rblk)) => Some(rblk.decode().map_err(|e| e.into())),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cardano::block::HeaderHash;
+    use cbor_event::se::Serializer;
+    use rand;
+    use std::env;
+    use storage_units::utils::magic;
+    use test_utils::boundary_block;
+    use types::header_to_blockhash;
+    use {Storage, StorageConfig};
+
+    #[test]
+    fn pack_reader() {
+        let mut dir = env::temp_dir();
+        dir.push(format!("cardano-storage-{}", rand::random::<u64>()));
+        let config = StorageConfig::new(&dir);
+        Storage::init(&config).unwrap();
+
+        let blk1 = boundary_block(HeaderHash::new(b"genesis"), 0);
+        let blk2 = boundary_block(blk1.header_hash(), 1);
+
+        let mut writer = packwriter_init(&config).unwrap();
+        let mut offsets = vec![];
+        for blk in [&blk1, &blk2].iter() {
+            let mut se = Serializer::new_vec();
+            se.serialize(*blk).unwrap();
+            offsets.push(writer.pos());
+            writer
+                .append(&header_to_blockhash(&blk.header_hash()), &se.finalize())
+                .unwrap();
+        }
+        let (packhash, _) = packwriter_finalize(&config, writer);
+        assert_eq!(offsets[0], magic::HEADER_SIZE as Offset);
+
+        let mut reader = PackReader::open(config.get_pack_filepath(&packhash)).unwrap();
+        let blk = reader.get_block_at(offsets[1]).unwrap();
+        assert_eq!(blk.header_hash(), blk2.header_hash());
+        let blk = reader.get_block_at(offsets[0]).unwrap();
+        assert_eq!(blk.header_hash(), blk1.header_hash());
+
+        let hashes: Vec<_> = reader
+            .iter()
+            .unwrap()
+            .map(|blk| blk.unwrap().header_hash())
+            .collect();
+        assert_eq!(hashes, vec![blk1.header_hash(), blk2.header_hash()]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,15 @@
+//! Helpers shared by the tests of the storage modules
+
+use cardano::block::{boundary, Block, ChainDifficulty, HeaderHash};
+use cardano::config::ProtocolMagic;
+
+/// a boundary block of epoch 0 following `previous`, blocks of
+/// different `previous` or `difficulty` have different header hashes.
+pub fn boundary_block(previous: HeaderHash, difficulty: u64) -> Block {
+    Block::BoundaryBlock(boundary::Block::new(
+        ProtocolMagic::default(),
+        previous,
+        vec![],
+        ChainDifficulty::from(difficulty),
+    ))
+}